@@ -0,0 +1,293 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::header,
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
+    routing::get,
+    Router,
+};
+use clap::Parser;
+use futures_util::stream::{self, Stream};
+use libft_docs::config::Config;
+use libft_docs::BuildOptions;
+use notify::{RecursiveMode, Watcher};
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+use tower_http::services::ServeDir;
+
+// Injected before `</body>` of every served page while watching
+const RELOAD_SNIPPET: &str = r#"<script>
+	new EventSource('/__livereload').addEventListener('reload', () => location.reload());
+</script>
+"#;
+
+// Used when neither `--port` nor docs.toml sets one
+const DEFAULT_PORT: u16 = 3000;
+
+// Wait this long after the first change so a save burst triggers one rebuild
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+#[derive(Parser)]
+#[command(name = "dev-server")]
+#[command(about = "Serve the generated documentation, optionally rebuilding on change")]
+struct Args {
+    /// libft source tree to watch; sources, headers, manuals and SCSS changes trigger a rebuild
+    #[arg(short, long, value_name = "LIBFT")]
+    watch: Option<String>,
+
+    /// Settings file [default: docs.toml in the working or watched directory]
+    #[arg(long, value_name = "FILE")]
+    config: Option<String>,
+
+    /// [default: `output.dir` of docs.toml, else dist]
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// [default: `server.port` of docs.toml, else 3000]
+    #[arg(short, long)]
+    port: Option<u16>,
+
+    /// Also list `static` helper functions
+    #[arg(long)]
+    include_internal: bool,
+
+    /// List public functions only, even when docs.toml sets `output.include_internal`
+    #[arg(long, conflicts_with = "include_internal")]
+    no_include_internal: bool,
+
+    /// Directory of Handlebars templates and assets overriding the built-in theme
+    #[arg(long, value_name = "DIR")]
+    theme: Option<String>,
+
+    /// Put everything in one index.html with hash routing instead of a page per category and function
+    #[arg(long)]
+    single_page: bool,
+
+    /// Write a page per category and function, even when docs.toml sets `output.single_page`
+    #[arg(long, conflicts_with = "single_page")]
+    no_single_page: bool,
+
+    /// Embed fonts and icons so the site makes no external requests (the default)
+    #[arg(long)]
+    offline: bool,
+
+    /// Load fonts and icons from Google Fonts and cdnjs instead of embedding them
+    #[arg(long, conflicts_with = "offline")]
+    cdn: bool,
+}
+
+// What a batch of file events requires
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Change {
+    None,
+    // SCSS only: recompile and copy the stylesheet
+    Styles,
+    // sources, manuals or templates: run the whole pipeline
+    Site,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref(), args.watch.as_deref())?;
+    let port = args.port.or(config.server.port).unwrap_or(DEFAULT_PORT);
+    let (reload_tx, _) = broadcast::channel::<()>(16);
+
+    // docs.toml, with the flags given on the command line taking precedence
+    let mut options = BuildOptions::from_config(config);
+    if let Some(output) = args.output {
+        options.output = output;
+    }
+    if args.theme.is_some() {
+        options.theme = args.theme;
+    }
+    if args.cdn || args.offline {
+        options.offline = !args.cdn;
+    }
+    if args.include_internal || args.no_include_internal {
+        options.include_internal = args.include_internal;
+    }
+    if args.single_page || args.no_single_page {
+        options.single_page = args.single_page;
+    }
+    // warnings only point at what to fix while editing
+    options.strict = false;
+    options.run_examples = false;
+
+    let mut app: Router<broadcast::Sender<()>> = Router::new()
+        .route("/", get(|| async { "Libft Documentation Server - Go to /dist for docs" }))
+        .nest_service("/static", ServeDir::new("static"))
+        .nest_service("/dist", ServeDir::new(&options.output));
+
+    if let Some(source) = &args.watch {
+        options.source = source.clone();
+        // like a failed rebuild: keep serving, the next save may fix it
+        if let Err(err) = libft_docs::build(&options) {
+            eprintln!("❌ Build failed: {:#}", err);
+        }
+        spawn_watcher(options, reload_tx.clone())?;
+
+        app = app
+            .route("/__livereload", get(livereload))
+            .layer(middleware::from_fn(inject_reload));
+        println!("👀 Watching {} for changes", source);
+    }
+
+    let app = app.with_state(reload_tx);
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    println!("🚀 Dev server running on http://localhost:{}", port);
+    println!("📖 Documentation available at http://localhost:{}/dist", port);
+
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+fn spawn_watcher(options: BuildOptions, reload: broadcast::Sender<()>) -> anyhow::Result<()> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<PathBuf>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            for path in event.paths {
+                let _ = tx.send(path);
+            }
+        }
+    })?;
+
+    let scss = scss_dir();
+    let mut roots = vec![PathBuf::from(&options.source)];
+    if scss.is_dir() {
+        roots.push(scss.clone());
+    }
+    if let Some(theme) = &options.theme {
+        roots.push(PathBuf::from(theme));
+    }
+    for root in &roots {
+        watcher.watch(root, RecursiveMode::Recursive)?;
+    }
+
+    // writing the site or the cache must not trigger another rebuild
+    let output = absolute(Path::new(&options.output));
+    let cache = absolute(Path::new(&options.cache_dir));
+
+    tokio::spawn(async move {
+        // keep the watcher alive as long as the loop runs
+        let _watcher = watcher;
+        while let Some(path) = rx.recv().await {
+            let mut change = classify(&path, &[&output, &cache], &options);
+            tokio::time::sleep(DEBOUNCE).await;
+            while let Ok(path) = rx.try_recv() {
+                change = change.max(classify(&path, &[&output, &cache], &options));
+            }
+            if change == Change::None {
+                continue;
+            }
+
+            let started = Instant::now();
+            let opts = options.clone();
+            let result = tokio::task::spawn_blocking(move || rebuild(&opts, change)).await;
+            match result {
+                Ok(Ok(())) => {
+                    println!("🔄 Rebuilt in {} ms", started.elapsed().as_millis());
+                    let _ = reload.send(());
+                }
+                Ok(Err(err)) => eprintln!("❌ Rebuild failed: {:#}", err),
+                Err(err) => eprintln!("❌ Rebuild panicked: {}", err),
+            }
+        }
+    });
+    Ok(())
+}
+
+fn classify(path: &Path, ignored: &[&Path], options: &BuildOptions) -> Change {
+    let path = absolute(path);
+    if ignored.iter().any(|dir| path.starts_with(dir)) {
+        return Change::None;
+    }
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("c" | "h" | "json" | "md") => Change::Site,
+        Some("hbs") => Change::Site,
+        Some("scss") => Change::Styles,
+        // theme assets such as a replacement styles.css
+        Some(_) if options.theme.as_deref().is_some_and(|t| path.starts_with(absolute(Path::new(t)))) => Change::Site,
+        _ => Change::None,
+    }
+}
+
+fn rebuild(options: &BuildOptions, change: Change) -> anyhow::Result<()> {
+    match change {
+        Change::None => Ok(()),
+        Change::Styles => {
+            compile_scss();
+            libft_docs::renderer::copy_stylesheet(Path::new(&options.output))
+        }
+        Change::Site => libft_docs::build(options).map(|_| ()),
+    }
+}
+
+fn scss_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("static").join("scss")
+}
+
+// main.css is committed; refresh it when a Sass compiler is installed
+fn compile_scss() {
+    let dir = scss_dir();
+    let status = std::process::Command::new("sass")
+        .arg(dir.join("main.scss"))
+        .arg(dir.join("main.css"))
+        .status();
+    match status {
+        Ok(s) if s.success() => println!("🎨 Compiled SCSS"),
+        Ok(s) => eprintln!("⚠️  sass exited with {}", s),
+        Err(_) => eprintln!("⚠️  sass not found, serving the last compiled main.css"),
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+async fn livereload(
+    State(reload): State<broadcast::Sender<()>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = stream::unfold(reload.subscribe(), |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(()) => return Some((Ok(Event::default().event("reload").data("")), rx)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+async fn inject_reload(req: Request, next: Next) -> Response {
+    let res = next.run(req).await;
+    let is_html = res.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/html"));
+    if !is_html {
+        return res;
+    }
+
+    let (mut parts, body) = res.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let html = String::from_utf8_lossy(&bytes);
+    let html = match html.rfind("</body>") {
+        Some(i) => format!("{}{}{}", &html[..i], RELOAD_SNIPPET, &html[i..]),
+        None => format!("{}{}", html, RELOAD_SNIPPET),
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(html))
+}
@@ -1,24 +1,295 @@
-use libft_docs::*;
-use clap::Parser;
-use anyhow::Result;
+use clap::{Args, Parser, Subcommand};
+use libft_docs::config::Config;
+use libft_docs::{coverage, diagnostics, examples, prototypes, scaffold, BuildOptions, LibftParser};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    #[arg(short, long, default_value = "../")]
-    source: String,
-    
-    #[arg(short, long, default_value = "./output")]
-    output: String,
-}
-
-fn main() -> Result<()> {
-    let args = Args::parse();
-    
-    println!("🔍 Scanning libft source at: {}", args.source);
-    println!("📝 Generating docs to: {}", args.output);
-    
-    // Your documentation generation logic here
-    
+#[command(name = "doc-generator")]
+#[command(about = "Generate documentation for libft")]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    build: BuildArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Report documentation coverage and fail below a threshold
+    Check(CheckArgs),
+    /// Write JSON manual and markdown stubs for functions without a manual
+    Scaffold(ScaffoldArgs),
+}
+
+#[derive(Args)]
+struct BuildArgs {
+    /// Settings file [default: docs.toml in the working or source directory]
+    #[arg(long, value_name = "FILE")]
+    config: Option<String>,
+
+    /// libft source tree [default: `source.dir` of docs.toml, else .]
+    #[arg(short, long)]
+    source: Option<String>,
+
+    /// [default: `output.dir` of docs.toml, else dist]
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// Also list `static` helper functions
+    #[arg(long)]
+    include_internal: bool,
+
+    /// List public functions only, even when docs.toml sets `output.include_internal`
+    #[arg(long, conflicts_with = "include_internal")]
+    no_include_internal: bool,
+
+    /// Directory of Handlebars templates and assets overriding the built-in theme
+    #[arg(long, value_name = "DIR")]
+    theme: Option<String>,
+
+    /// Put everything in one index.html with hash routing instead of a page per category and function
+    #[arg(long)]
+    single_page: bool,
+
+    /// Write a page per category and function, even when docs.toml sets `output.single_page`
+    #[arg(long, conflicts_with = "single_page")]
+    no_single_page: bool,
+
+    /// Embed fonts and icons so the site makes no external requests (the default)
+    #[arg(long)]
+    offline: bool,
+
+    /// Load fonts and icons from Google Fonts and cdnjs instead of embedding them
+    #[arg(long, conflicts_with = "offline")]
+    cdn: bool,
+
+    /// Fail on manual warnings (orphan manuals, unresolved see_also/related) and header/definition mismatches as well as errors
+    #[arg(long)]
+    strict: bool,
+
+    /// Fail on errors only, even when docs.toml sets `output.strict`
+    #[arg(long, conflicts_with = "strict")]
+    no_strict: bool,
+
+    /// Compile and run the examples against libft.a, recording or checking their output
+    #[arg(long)]
+    run_examples: bool,
+
+    /// Seconds each example may run before it counts as failed
+    #[arg(long, value_name = "SECS", default_value_t = 5)]
+    example_timeout: u64,
+
+    /// Parse every file again instead of reusing the results cached from the previous build
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Where parse results are cached between builds [default: `output.cache_dir` of docs.toml, else target/libft-docs]
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<String>,
+
+    /// Threads parsing files [default: one per CPU]
+    #[arg(short, long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// Settings file [default: docs.toml in the working or source directory]
+    #[arg(long, value_name = "FILE")]
+    config: Option<String>,
+
+    /// libft source tree [default: `source.dir` of docs.toml, else .]
+    #[arg(short, long)]
+    source: Option<String>,
+
+    /// Also measure `static` helper functions
+    #[arg(long)]
+    include_internal: bool,
+
+    /// Measure public functions only, even when docs.toml sets `output.include_internal`
+    #[arg(long, conflicts_with = "include_internal")]
+    no_include_internal: bool,
+
+    /// Fail when overall coverage (percent of documented aspects) is below this
+    #[arg(long, value_name = "PERCENT", default_value_t = 0.0)]
+    min_coverage: f64,
+
+    /// Write the report as JSON
+    #[arg(long, value_name = "FILE")]
+    json: Option<String>,
+
+    /// Write the report as JUnit XML, one failing test per function below --min-coverage
+    #[arg(long, value_name = "FILE")]
+    junit: Option<String>,
+
+    /// Fail on any manual diagnostic, warnings included
+    #[arg(long)]
+    strict: bool,
+
+    /// Fail on manual errors only, even when docs.toml sets `output.strict`
+    #[arg(long, conflicts_with = "strict")]
+    no_strict: bool,
+
+    /// Compile and run the examples against libft.a and fail on a wrong output
+    #[arg(long)]
+    run_examples: bool,
+
+    /// Seconds each example may run before it counts as failed
+    #[arg(long, value_name = "SECS", default_value_t = 5)]
+    example_timeout: u64,
+
+    /// Threads parsing files [default: one per CPU]
+    #[arg(short, long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+}
+
+#[derive(Args)]
+struct ScaffoldArgs {
+    /// Settings file [default: docs.toml in the working or source directory]
+    #[arg(long, value_name = "FILE")]
+    config: Option<String>,
+
+    /// libft source tree [default: `source.dir` of docs.toml, else .]
+    #[arg(short, long)]
+    source: Option<String>,
+
+    /// Where to write the stubs [default: the first existing manual directory, else docs/man of the libft tree]
+    #[arg(short, long, value_name = "DIR")]
+    dir: Option<String>,
+
+    /// Also scaffold `static` helper functions
+    #[arg(long)]
+    include_internal: bool,
+
+    /// Scaffold public functions only, even when docs.toml sets `output.include_internal`
+    #[arg(long, conflicts_with = "include_internal")]
+    no_include_internal: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Check(args)) => check(args),
+        Some(Command::Scaffold(args)) => scaffold(args),
+        None => generate(cli.build),
+    }
+}
+
+// docs.toml, with the flags given on the command line taking precedence
+fn generate(args: BuildArgs) -> anyhow::Result<()> {
+    let mut options = BuildOptions::from_config(Config::load(args.config.as_deref(), args.source.as_deref())?);
+    if let Some(source) = args.source {
+        options.source = source;
+    }
+    if let Some(output) = args.output {
+        options.output = output;
+    }
+    if args.theme.is_some() {
+        options.theme = args.theme;
+    }
+    if args.cdn || args.offline {
+        options.offline = !args.cdn;
+    }
+    options.include_internal = flag(args.include_internal, args.no_include_internal, options.include_internal);
+    options.single_page = flag(args.single_page, args.no_single_page, options.single_page);
+    options.strict = flag(args.strict, args.no_strict, options.strict);
+    options.run_examples = args.run_examples;
+    options.example_timeout = args.example_timeout;
+    options.incremental = !args.no_cache;
+    if let Some(cache_dir) = args.cache_dir {
+        options.cache_dir = cache_dir;
+    }
+    options.jobs = args.jobs.map(usize::from);
+
+    println!("🔍 Parsing libft source code from: {}", options.source);
+    libft_docs::build(&options)?;
+
+    println!("✅ Documentation generated in: {}", options.output);
+
+    Ok(())
+}
+
+// `--x` or `--no-x` when given, else what docs.toml says
+fn flag(yes: bool, no: bool, config: bool) -> bool {
+    if yes || no { yes } else { config }
+}
+
+fn check(args: CheckArgs) -> anyhow::Result<()> {
+    let config = Config::load(args.config.as_deref(), args.source.as_deref())?;
+    let source = args.source.or_else(|| config.source.dir.clone()).unwrap_or_else(|| ".".to_string());
+    let include_internal = flag(args.include_internal, args.no_include_internal, config.output.include_internal);
+    let strict = flag(args.strict, args.no_strict, config.output.strict);
+    let selection = config.selection()?;
+
+    println!("🔍 Checking documentation coverage of: {}", source);
+    let mut parser = LibftParser::new(source.clone()).with_config(config)?;
+    if let Some(jobs) = args.jobs {
+        parser = parser.with_jobs(jobs.into());
+    }
+    let mut metadata = parser.parse()?;
+    let manuals = diagnostics::report(&metadata.diagnostics, strict);
+    let prototypes = prototypes::report(&prototypes::check(Path::new(&source), &selection));
+    let examples = if args.run_examples {
+        let timeout = Duration::from_secs(args.example_timeout);
+        examples::run(&mut metadata, Path::new(&source), &selection, timeout)?.report()
+    } else {
+        Ok(())
+    };
+    let report = coverage::compute(&metadata, include_internal);
+
+    println!();
+    print!("{}", report.table());
+    println!();
+    let untested = report.untested();
+    if !untested.is_empty() {
+        println!("🧪 {} functions without tests: {}", untested.len(), untested.join(", "));
+    }
+
+    if let Some(path) = &args.json {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        println!("📄 JSON report written to: {}", path);
+    }
+    if let Some(path) = &args.junit {
+        std::fs::write(path, report.junit(args.min_coverage))?;
+        println!("📄 JUnit report written to: {}", path);
+    }
+
+    manuals?;
+    prototypes?;
+    examples?;
+    if report.total.percent < args.min_coverage {
+        anyhow::bail!("documentation coverage {:.1}% is below the required {:.1}%",
+            report.total.percent, args.min_coverage);
+    }
+    println!("✅ Documentation coverage: {:.1}%", report.total.percent);
+    Ok(())
+}
+
+fn scaffold(args: ScaffoldArgs) -> anyhow::Result<()> {
+    let config = Config::load(args.config.as_deref(), args.source.as_deref())?;
+    let source = args.source.or_else(|| config.source.dir.clone()).unwrap_or_else(|| ".".to_string());
+    let include_internal = flag(args.include_internal, args.no_include_internal, config.output.include_internal);
+    let dir = match &args.dir {
+        Some(dir) => PathBuf::from(dir),
+        // where the manuals already are, so the next build finds no duplicates
+        None => {
+            let dirs = config.manual_dirs(Path::new(&source));
+            dirs.iter().find(|d| d.is_dir()).unwrap_or(&dirs[0]).clone()
+        }
+    };
+
+    println!("🔍 Parsing libft source code from: {}", source);
+    let metadata = LibftParser::new(source).with_config(config)?.parse()?;
+    let summary = scaffold::scaffold(&metadata, &dir, include_internal)?;
+    for path in &summary.written {
+        println!("  📝 Scaffolded: {}", path.display());
+    }
+    for path in &summary.skipped {
+        println!("  ⏭️  Kept existing: {}", path.display());
+    }
+    println!("✅ Wrote {} stub files in: {}", summary.written.len(), dir.display());
     Ok(())
 }
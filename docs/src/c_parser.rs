@@ -0,0 +1,1222 @@
+//! Minimal C tokenizer and declaration parser.
+//!
+//! This is not a full C front-end: preprocessor directives are kept as opaque
+//! tokens and only top-level declarations are inspected. It is however good
+//! enough to recover function prototypes written across several lines, with
+//! storage-class qualifiers, function-pointer parameters or K&R-style
+//! parameter declarations.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident,
+    Number,
+    Str,
+    Char,
+    Punct,
+    Comment,
+    Directive,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    // 1-based line of the first character
+    pub line: usize,
+}
+
+impl Token {
+    fn is(&self, text: &str) -> bool {
+        self.kind == TokenKind::Punct && self.text == text
+    }
+
+    fn is_word(&self) -> bool {
+        self.kind == TokenKind::Ident
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CParam {
+    // empty for unnamed parameters (`int`, `t_list *`)
+    pub name: String,
+    pub type_name: String,
+}
+
+impl CParam {
+    /// Re-insert the name into the type: `void (*)(void *)` + `del` gives
+    /// `void (*del)(void *)`, `char []` + `buf` gives `char buf[]`.
+    pub fn declaration(&self) -> String {
+        if self.name.is_empty() {
+            return self.type_name.clone();
+        }
+        if let Some(pos) = self.type_name.find("(*") {
+            let (head, tail) = self.type_name.split_at(pos + 2);
+            return format!("{}{}{}", head, self.name, tail);
+        }
+        if let Some(pos) = self.type_name.find('[') {
+            let (head, tail) = self.type_name.split_at(pos);
+            return format!("{}{}", join_type_and_name(head.trim_end(), &self.name), tail);
+        }
+        join_type_and_name(&self.type_name, &self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionDecl {
+    pub name: String,
+    pub return_type: String,
+    pub parameters: Vec<CParam>,
+    // storage-class / function specifiers: static, inline, extern, ...
+    pub qualifiers: Vec<String>,
+    pub is_variadic: bool,
+    // true when followed by a body, false for a plain prototype
+    pub is_definition: bool,
+    // 1-based line where the declaration starts
+    pub line: usize,
+    // raw comment written directly above the declaration, delimiters included
+    pub doc_comment: Option<String>,
+}
+
+impl FunctionDecl {
+    pub fn is_static(&self) -> bool {
+        self.qualifiers.iter().any(|q| q == "static")
+    }
+}
+
+impl fmt::Display for FunctionDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut params: Vec<String> = self.parameters.iter().map(|p| p.declaration()).collect();
+        if self.is_variadic {
+            params.push("...".to_string());
+        }
+        if params.is_empty() {
+            params.push("void".to_string());
+        }
+        let mut head = self.qualifiers.join(" ");
+        if !head.is_empty() {
+            head.push(' ');
+        }
+        head.push_str(&join_type_and_name(&self.return_type, &self.name));
+        write!(f, "{}({})", head, params.join(", "))
+    }
+}
+
+fn join_type_and_name(type_name: &str, name: &str) -> String {
+    if type_name.ends_with('*') || type_name.is_empty() {
+        format!("{}{}", type_name, name)
+    } else {
+        format!("{} {}", type_name, name)
+    }
+}
+
+const STORAGE_QUALIFIERS: &[&str] = &[
+    "static", "inline", "extern", "__inline", "__inline__", "_Noreturn", "register",
+];
+
+const CV_QUALIFIERS: &[&str] = &["const", "volatile", "restrict", "__restrict", "__restrict__", "register"];
+
+const BUILTIN_TYPES: &[&str] = &[
+    "void", "char", "short", "int", "long", "float", "double", "signed", "unsigned",
+    "_Bool", "_Complex", "struct", "union", "enum",
+];
+
+const ATTRIBUTE_WORDS: &[&str] = &["__attribute__", "__attribute", "__asm__", "__asm", "asm", "__declspec"];
+
+const PUNCT3: &[&str] = &["...", "<<=", ">>="];
+const PUNCT2: &[&str] = &[
+    "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||", "+=", "-=", "*=",
+    "/=", "%=", "&=", "|=", "^=", "##",
+];
+
+/// Split C source into tokens. Comments and preprocessor directives are kept
+/// as single tokens so callers can attach documentation or skip them.
+pub fn tokenize(src: &str) -> Vec<Token> {
+    let b = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+    let mut at_line_start = true;
+
+    let push = |tokens: &mut Vec<Token>, kind, start: usize, end: usize, line| {
+        tokens.push(Token { kind, text: src[start..end].to_string(), line });
+    };
+
+    while i < b.len() {
+        let c = b[i];
+        if c == b'\n' {
+            line += 1;
+            at_line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let start_line = line;
+
+        if c == b'#' && at_line_start {
+            // directive runs to the end of line, honouring backslash continuations
+            while i < b.len() && b[i] != b'\n' {
+                if b[i] == b'\\' && i + 1 < b.len() && b[i + 1] == b'\n' {
+                    line += 1;
+                    i += 2;
+                    continue;
+                }
+                if b[i] == b'/' && i + 1 < b.len() && b[i + 1] == b'*' {
+                    i = skip_block_comment(b, i, &mut line);
+                    continue;
+                }
+                i += 1;
+            }
+            push(&mut tokens, TokenKind::Directive, start, i, start_line);
+            continue;
+        }
+        at_line_start = false;
+
+        if c == b'/' && b.get(i + 1) == Some(&b'/') {
+            while i < b.len() && b[i] != b'\n' {
+                i += 1;
+            }
+            push(&mut tokens, TokenKind::Comment, start, i, start_line);
+        } else if c == b'/' && b.get(i + 1) == Some(&b'*') {
+            i = skip_block_comment(b, i, &mut line);
+            push(&mut tokens, TokenKind::Comment, start, i, start_line);
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while i < b.len() && (b[i].is_ascii_alphanumeric() || b[i] == b'_') {
+                i += 1;
+            }
+            push(&mut tokens, TokenKind::Ident, start, i, start_line);
+        } else if c.is_ascii_digit() || (c == b'.' && b.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            while i < b.len() {
+                let d = b[i];
+                let exponent_sign = (d == b'+' || d == b'-') && matches!(b[i - 1], b'e' | b'E' | b'p' | b'P');
+                if exponent_sign || d.is_ascii_alphanumeric() || d == b'.' || d == b'_' {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            push(&mut tokens, TokenKind::Number, start, i, start_line);
+        } else if c == b'"' || c == b'\'' {
+            i += 1;
+            while i < b.len() && b[i] != c && b[i] != b'\n' {
+                if b[i] == b'\\' && i + 1 < b.len() {
+                    if b[i + 1] == b'\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(b.len());
+            let kind = if c == b'"' { TokenKind::Str } else { TokenKind::Char };
+            push(&mut tokens, kind, start, i, start_line);
+        } else {
+            let rest = &src[i..];
+            let len = PUNCT3
+                .iter()
+                .chain(PUNCT2.iter())
+                .find(|p| rest.starts_with(*p))
+                .map(|p| p.len())
+                .unwrap_or_else(|| rest.chars().next().map(char::len_utf8).unwrap_or(1));
+            i += len;
+            push(&mut tokens, TokenKind::Punct, start, i, start_line);
+        }
+    }
+    tokens
+}
+
+fn skip_block_comment(b: &[u8], mut i: usize, line: &mut usize) -> usize {
+    i += 2;
+    while i < b.len() {
+        if b[i] == b'*' && b.get(i + 1) == Some(&b'/') {
+            return i + 2;
+        }
+        if b[i] == b'\n' {
+            *line += 1;
+        }
+        i += 1;
+    }
+    b.len()
+}
+
+/// Index of the token closing the bracket opened at `open`, or the last index
+/// if the source is unbalanced.
+fn matching(tokens: &[&Token], open: usize) -> usize {
+    let (o, c) = match tokens[open].text.as_str() {
+        "(" => ("(", ")"),
+        "[" => ("[", "]"),
+        _ => ("{", "}"),
+    };
+    let mut depth = 0usize;
+    for (j, t) in tokens.iter().enumerate().skip(open) {
+        if t.is(o) {
+            depth += 1;
+        } else if t.is(c) {
+            depth -= 1;
+            if depth == 0 {
+                return j;
+            }
+        }
+    }
+    tokens.len() - 1
+}
+
+/// Code tokens, and for each of them the comment block written directly above
+/// it (a directive in between detaches the comment).
+fn split_comments(tokens: &[Token]) -> (Vec<&Token>, Vec<Option<String>>) {
+    let mut code = Vec::new();
+    let mut leading = Vec::new();
+    let mut pending: Option<String> = None;
+    let mut last_line = 0;
+    for t in tokens {
+        match t.kind {
+            TokenKind::Comment => {
+                // consecutive `//` lines form one block, otherwise keep the closest
+                pending = match pending {
+                    Some(prev) if t.text.starts_with("//") && prev.starts_with("//") && t.line == last_line + 1 => {
+                        Some(format!("{}\n{}", prev, t.text))
+                    }
+                    _ => Some(t.text.clone()),
+                };
+                last_line = t.line + t.text.matches('\n').count();
+            }
+            TokenKind::Directive => pending = None,
+            _ => {
+                code.push(t);
+                leading.push(pending.take());
+            }
+        }
+    }
+    (code, leading)
+}
+
+/// Lines of a stretch of code and the functions it calls, in order of first
+/// call.
+#[derive(Debug, Clone)]
+pub struct CodeSpan {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub calls: Vec<String>,
+}
+
+/// A function definition, from its return type to its closing brace, with
+/// the bare `{ ... }` blocks written directly in its body.
+#[derive(Debug, Clone)]
+pub struct FunctionBody {
+    pub name: String,
+    pub span: CodeSpan,
+    pub blocks: Vec<CodeSpan>,
+    // identifiers and keywords between the braces, sorted and deduplicated
+    pub words: Vec<String>,
+}
+
+/// Every function defined in `src`, with what its body calls.
+pub fn function_bodies(src: &str) -> Vec<FunctionBody> {
+    let tokens = tokenize(src);
+    let code: Vec<&Token> = tokens.iter()
+        .filter(|t| !matches!(t.kind, TokenKind::Comment | TokenKind::Directive))
+        .collect();
+    let mut out = Vec::new();
+    // definitions come in source order: each one starts after the previous body
+    let mut from = 0;
+    for decl in parse_functions(src).into_iter().filter(|d| d.is_definition) {
+        // the declaration's first token, then its name, which may sit on a
+        // later line than the return type
+        let Some(start) = code[from..].iter().position(|t| t.line >= decl.line).map(|i| from + i) else { continue };
+        let Some(at) = code[start..].windows(2)
+            .position(|w| w[0].is_word() && w[0].text == decl.name && w[1].is("("))
+            .map(|i| start + i) else { continue };
+        let Some(open) = code[at..].iter().position(|t| t.is("{")).map(|i| at + i) else { continue };
+        let close = matching(&code, open);
+        from = close + 1;
+
+        let mut blocks = Vec::new();
+        let mut i = open + 1;
+        while i < close {
+            if code[i].is("{") {
+                let end = matching(&code, i);
+                // a bare block, not the body of an `if`, `while` or `else`
+                if code[i - 1].is(";") || code[i - 1].is("{") || code[i - 1].is("}") {
+                    blocks.push(code_span(&code[i..=end]));
+                }
+                i = end + 1;
+            } else {
+                i += 1;
+            }
+        }
+        let mut words: Vec<String> = code[open..=close].iter()
+            .filter(|t| t.is_word())
+            .map(|t| t.text.clone())
+            .collect();
+        words.sort();
+        words.dedup();
+        let mut span = code_span(&code[start..=close]);
+        span.calls.retain(|c| *c != decl.name);
+        out.push(FunctionBody { name: decl.name, span, blocks, words });
+    }
+    out
+}
+
+fn code_span(tokens: &[&Token]) -> CodeSpan {
+    let mut calls: Vec<String> = Vec::new();
+    for pair in tokens.windows(2) {
+        if pair[0].is_word() && pair[1].is("(") && !is_keyword(&pair[0].text) && !calls.contains(&pair[0].text) {
+            calls.push(pair[0].text.clone());
+        }
+    }
+    CodeSpan {
+        first_line: tokens.first().map_or(1, |t| t.line),
+        last_line: tokens.last().map_or(1, |t| t.line),
+        calls,
+    }
+}
+
+/// Every top-level function declaration or definition found in `src`.
+pub fn parse_functions(src: &str) -> Vec<FunctionDecl> {
+    let tokens = tokenize(src);
+    let (code, leading) = split_comments(&tokens);
+    let with_doc = |mut func: FunctionDecl, start: usize| {
+        func.doc_comment = leading.get(start).cloned().flatten();
+        func
+    };
+
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < code.len() {
+        let t = code[i];
+        if t.is("(") || t.is("[") {
+            let close = matching(&code, i);
+            if let Some((func, body)) = parse_knr_definition(&code, start, close) {
+                out.push(with_doc(func, start));
+                start = body + 1;
+                i = start;
+                continue;
+            }
+            i = close + 1;
+            continue;
+        }
+        if t.is(";") {
+            let decl = &code[start..i];
+            if let Some(func) = parse_declaration(decl, false) {
+                out.push(with_doc(func, start));
+            }
+            start = i + 1;
+        } else if t.is("{") {
+            let decl = &code[start..i];
+            // `extern "C" {` wraps declarations, step inside it
+            if decl.len() == 2 && decl[0].text == "extern" && decl[1].kind == TokenKind::Str {
+                start = i + 1;
+                i += 1;
+                continue;
+            }
+            let end = matching(&code, i);
+            if let Some(func) = parse_declaration(decl, true) {
+                out.push(with_doc(func, start));
+                start = end + 1;
+            }
+            // otherwise a struct/enum body or an initializer: keep the
+            // declaration open until its terminating `;`
+            i = end + 1;
+            continue;
+        } else if t.is("}") {
+            start = i + 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Old-style definition: `int f(a, b) int a; char *b; { ... }`. `close` is
+/// the index of the `)` ending the identifier list. Returns the function and
+/// the index of the closing brace of its body.
+fn parse_knr_definition(code: &[&Token], start: usize, close: usize) -> Option<(FunctionDecl, usize)> {
+    // a type must follow the head, otherwise this is a prototype or a call
+    if !code.get(close + 1).is_some_and(|t| t.is_word() && !ATTRIBUTE_WORDS.contains(&t.text.as_str())) {
+        return None;
+    }
+    let head = &code[start..=close];
+    // the parameter list must be bare identifiers: `(a, b)`
+    let open = head.iter().rposition(|t| t.is("("))?;
+    let names = split_commas(&head[open + 1..head.len() - 1]);
+    if names.is_empty() || names.iter().any(|p| p.len() != 1 || !p[0].is_word() || is_keyword(&p[0].text)) {
+        return None;
+    }
+    let mut func = parse_declaration(head, true)?;
+
+    let mut i = close + 1;
+    let mut seg_start = i;
+    while i < code.len() && !code[i].is("{") {
+        if code[i].is("(") || code[i].is("[") {
+            i = matching(code, i) + 1;
+            continue;
+        }
+        if code[i].is(";") {
+            if !apply_knr_types(&mut func, &code[seg_start..i]) {
+                return None;
+            }
+            seg_start = i + 1;
+        }
+        i += 1;
+    }
+    if i >= code.len() || seg_start != i {
+        return None;
+    }
+    Some((func, matching(code, i)))
+}
+
+// `char *a, **b;` gives its type to each parameter it names
+fn apply_knr_types(func: &mut FunctionDecl, seg: &[&Token]) -> bool {
+    let params = split_declarators(seg);
+    if params.is_empty() {
+        return false;
+    }
+    for param in params {
+        match func.parameters.iter_mut().find(|p| p.name == param.name) {
+            Some(slot) => *slot = param,
+            None => return false,
+        }
+    }
+    true
+}
+
+fn split_commas<'a, 'b>(tokens: &'b [&'a Token]) -> Vec<&'b [&'a Token]> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut last = 0;
+    for (j, t) in tokens.iter().enumerate() {
+        if t.is("(") || t.is("[") {
+            depth += 1;
+        } else if t.is(")") || t.is("]") {
+            depth -= 1;
+        } else if t.is(",") && depth == 0 {
+            parts.push(&tokens[last..j]);
+            last = j + 1;
+        }
+    }
+    if last < tokens.len() {
+        parts.push(&tokens[last..]);
+    }
+    parts
+}
+
+fn strip_attributes<'a>(decl: &[&'a Token]) -> Vec<&'a Token> {
+    let mut out = Vec::with_capacity(decl.len());
+    let mut i = 0;
+    while i < decl.len() {
+        if decl[i].is_word() && ATTRIBUTE_WORDS.contains(&decl[i].text.as_str()) {
+            if decl.get(i + 1).is_some_and(|t| t.is("(")) {
+                i = matching(decl, i + 1) + 1;
+            } else {
+                i += 1;
+            }
+            continue;
+        }
+        if decl[i].is_word() && (decl[i].text == "__extension__" || decl[i].text == "__restrict") {
+            i += 1;
+            continue;
+        }
+        out.push(decl[i]);
+        i += 1;
+    }
+    out
+}
+
+fn parse_declaration(decl: &[&Token], is_definition: bool) -> Option<FunctionDecl> {
+    let decl = strip_attributes(decl);
+    let first = decl.first()?;
+    if first.text == "typedef" || decl.iter().any(|t| t.is("=")) {
+        return None;
+    }
+
+    // the function name is the identifier directly before the parameter list
+    let mut name_idx = None;
+    let mut j = 0;
+    while j < decl.len() {
+        if decl[j].is("(") {
+            if j > 0 && decl[j - 1].is_word() && !is_keyword(&decl[j - 1].text) {
+                name_idx = Some(j - 1);
+                break;
+            }
+            j = matching(&decl, j) + 1;
+            continue;
+        }
+        j += 1;
+    }
+    let name_idx = name_idx?;
+    let close = matching(&decl, name_idx + 1);
+    // anything after the parameter list means this is not a plain function
+    if close + 1 != decl.len() {
+        return None;
+    }
+
+    let mut qualifiers = Vec::new();
+    let mut ret = Vec::new();
+    for t in &decl[..name_idx] {
+        if STORAGE_QUALIFIERS.contains(&t.text.as_str()) && t.text != "register" {
+            qualifiers.push(t.text.clone());
+        } else {
+            ret.push(*t);
+        }
+    }
+    if ret.iter().any(|t| t.kind != TokenKind::Ident && !t.is("*")) {
+        return None;
+    }
+    let mut return_type = join_tokens(&ret);
+    if return_type.is_empty() {
+        // implicit int, as in old-style definitions
+        return_type = "int".to_string();
+    }
+
+    let mut parameters = Vec::new();
+    let mut is_variadic = false;
+    let inner = &decl[name_idx + 2..close];
+    let parts = split_commas(inner);
+    for part in &parts {
+        if part.len() == 1 && part[0].is("...") {
+            is_variadic = true;
+        } else if part.len() == 1 && part[0].text == "void" && parts.len() == 1 {
+            // `(void)`: no parameters
+        } else if !part.is_empty() {
+            parameters.push(parse_param(part, is_definition));
+        }
+    }
+
+    Some(FunctionDecl {
+        name: decl[name_idx].text.clone(),
+        return_type,
+        parameters,
+        qualifiers,
+        is_variadic,
+        is_definition,
+        line: first.line,
+        doc_comment: None,
+    })
+}
+
+/// `lone_is_name` decides how a single identifier is read: a parameter name in
+/// definitions (`f(a, b)` K&R style), a type in prototypes (`f(t_list)`).
+fn parse_param(tokens: &[&Token], lone_is_name: bool) -> CParam {
+    // function pointer: `void (*del)(void *)`
+    for j in 0..tokens.len().saturating_sub(3) {
+        if tokens[j].is("(") && tokens[j + 1].is("*") && tokens[j + 2].is_word() && tokens[j + 3].is(")") {
+            let mut rest: Vec<&Token> = tokens[..j + 2].to_vec();
+            rest.extend_from_slice(&tokens[j + 3..]);
+            return CParam { name: tokens[j + 2].text.clone(), type_name: join_tokens(&rest) };
+        }
+    }
+
+    let end = tokens.iter().position(|t| t.is("[")).unwrap_or(tokens.len());
+    let words: Vec<&&Token> = tokens[..end]
+        .iter()
+        .filter(|t| t.is_word() && !CV_QUALIFIERS.contains(&t.text.as_str()))
+        .collect();
+    let last_word = tokens[..end].iter().rposition(|t| t.is_word());
+    if let Some(k) = last_word {
+        let is_name = if words.len() >= 2 {
+            !BUILTIN_TYPES.contains(&tokens[k].text.as_str())
+        } else {
+            lone_is_name && tokens.len() == 1 && !is_keyword(&tokens[k].text)
+        };
+        if is_name {
+            let mut rest: Vec<&Token> = tokens[..k].to_vec();
+            rest.extend_from_slice(&tokens[k + 1..]);
+            let mut type_name = join_tokens(&rest);
+            if type_name.is_empty() || type_name.starts_with('[') {
+                type_name = format!("int{}", type_name);
+            }
+            return CParam { name: tokens[k].text.clone(), type_name };
+        }
+    }
+    CParam { name: String::new(), type_name: join_tokens(tokens) }
+}
+
+fn is_keyword(word: &str) -> bool {
+    BUILTIN_TYPES.contains(&word)
+        || CV_QUALIFIERS.contains(&word)
+        || STORAGE_QUALIFIERS.contains(&word)
+        || ATTRIBUTE_WORDS.contains(&word)
+        || matches!(word, "return" | "sizeof" | "if" | "while" | "for" | "switch" | "typedef")
+}
+
+/// Render tokens back to source with conventional C spacing
+/// (`const char *`, `void (*)(void *)`, `int[10]`).
+fn join_tokens(tokens: &[&Token]) -> String {
+    let mut out = String::new();
+    let mut prev: Option<&Token> = None;
+    for t in tokens {
+        if let Some(p) = prev {
+            let tight = p.is("(")
+                || p.is("[")
+                || p.is("*")
+                || t.is(")")
+                || t.is("]")
+                || t.is(",")
+                || t.is("[")
+                || (t.is("(") && !p.is_word());
+            if !tight {
+                out.push(' ');
+            }
+        }
+        out.push_str(&t.text);
+        prev = Some(t);
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeDeclKind {
+    Struct,
+    Union,
+    Enum,
+    Typedef,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDecl {
+    pub name: String,
+    pub type_name: String,
+    pub doc_comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumValueDecl {
+    pub name: String,
+    // initializer expression as written, if any
+    pub value: Option<String>,
+    pub doc_comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDecl {
+    // typedef name when there is one, struct/union/enum tag otherwise
+    pub name: String,
+    pub kind: TypeDeclKind,
+    // `s_list` in `typedef struct s_list {...} t_list`
+    pub tag: Option<String>,
+    // aliased type for plain typedefs: `struct s_list`, `int (*)(void *)`
+    pub underlying: Option<String>,
+    // declared through `typedef` rather than as a bare tag
+    pub is_typedef: bool,
+    pub fields: Vec<FieldDecl>,
+    pub values: Vec<EnumValueDecl>,
+    pub line: usize,
+    pub doc_comment: Option<String>,
+}
+
+impl TypeDecl {
+    /// The declaration rendered back to C, one field or value per line.
+    pub fn definition(&self) -> String {
+        let keyword = match self.kind {
+            TypeDeclKind::Struct => "struct",
+            TypeDeclKind::Union => "union",
+            TypeDeclKind::Enum => "enum",
+            TypeDeclKind::Typedef => {
+                let alias = CParam {
+                    name: self.name.clone(),
+                    type_name: self.underlying.clone().unwrap_or_default(),
+                };
+                return format!("typedef {};", alias.declaration());
+            }
+        };
+        let typedef = self.is_typedef;
+        let mut out = String::new();
+        if typedef {
+            out.push_str("typedef ");
+        }
+        out.push_str(keyword);
+        if let Some(tag) = self.tag.as_ref().or((!typedef).then_some(&self.name)) {
+            out.push(' ');
+            out.push_str(tag);
+        }
+        out.push_str(" {\n");
+        if self.kind == TypeDeclKind::Enum {
+            let values: Vec<String> = self
+                .values
+                .iter()
+                .map(|v| match &v.value {
+                    Some(val) => format!("    {} = {}", v.name, val),
+                    None => format!("    {}", v.name),
+                })
+                .collect();
+            out.push_str(&values.join(",\n"));
+            out.push('\n');
+        } else {
+            for f in &self.fields {
+                let field = CParam { name: f.name.clone(), type_name: f.type_name.clone() };
+                out.push_str(&format!("    {};\n", field.declaration()));
+            }
+        }
+        out.push('}');
+        if typedef {
+            out.push(' ');
+            out.push_str(&self.name);
+        }
+        out.push(';');
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroDecl {
+    pub name: String,
+    // `Some` for function-like macros, even when empty: `#define F() ...`
+    pub parameters: Option<Vec<String>>,
+    pub body: String,
+    pub line: usize,
+    pub doc_comment: Option<String>,
+}
+
+impl MacroDecl {
+    pub fn definition(&self) -> String {
+        let mut out = format!("#define {}", self.name);
+        if let Some(params) = &self.parameters {
+            out.push_str(&format!("({})", params.join(", ")));
+        }
+        if !self.body.is_empty() {
+            out.push(' ');
+            out.push_str(&self.body);
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncludeDecl {
+    // between the quotes or brackets
+    pub path: String,
+    // `#include <...>`
+    pub system: bool,
+    pub line: usize,
+}
+
+/// Every top-level struct, union, enum and typedef declared in `src`.
+pub fn parse_types(src: &str) -> Vec<TypeDecl> {
+    let tokens = tokenize(src);
+    let (code, leading) = split_comments(&tokens);
+
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < code.len() {
+        let t = code[i];
+        if t.is("(") || t.is("[") {
+            i = matching(&code, i) + 1;
+            continue;
+        }
+        if t.is("{") {
+            let end = matching(&code, i);
+            let decl = &code[start..i];
+            let is_extern_c = decl.len() == 2 && decl[0].text == "extern" && decl[1].kind == TokenKind::Str;
+            if is_extern_c {
+                start = i + 1;
+            } else if parse_declaration(decl, true).is_some() {
+                // function body
+                start = end + 1;
+            }
+            i = if is_extern_c { i + 1 } else { end + 1 };
+            continue;
+        }
+        if t.is(";") {
+            out.extend(parse_type_statement(&code, &leading, start, i));
+            start = i + 1;
+        } else if t.is("}") {
+            start = i + 1;
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Types declared by the statement `code[start..end]` (`end` is its `;`).
+fn parse_type_statement(code: &[&Token], leading: &[Option<String>], start: usize, end: usize) -> Vec<TypeDecl> {
+    let stmt = &code[start..end];
+    let Some(first) = stmt.first() else { return Vec::new() };
+    let doc = leading.get(start).cloned().flatten();
+    let is_typedef = first.text == "typedef";
+    let spec = if is_typedef { 1 } else { 0 };
+
+    let kind = match stmt.get(spec).map(|t| t.text.as_str()) {
+        Some("struct") => Some(TypeDeclKind::Struct),
+        Some("union") => Some(TypeDeclKind::Union),
+        Some("enum") => Some(TypeDeclKind::Enum),
+        _ => None,
+    };
+    let open = stmt.iter().position(|t| t.is("{"));
+
+    let mut out = Vec::new();
+    match (kind, open) {
+        (Some(kind), Some(open)) => {
+            let close = matching(stmt, open);
+            let tag = stmt[spec + 1..open].iter().find(|t| t.is_word()).map(|t| t.text.clone());
+            let (fields, values) = if kind == TypeDeclKind::Enum {
+                (Vec::new(), parse_enum_values(code, leading, start + open + 1, start + close))
+            } else {
+                (parse_fields(code, leading, start + open + 1, start + close), Vec::new())
+            };
+            let declarators = if is_typedef { split_commas(&stmt[close + 1..]) } else { Vec::new() };
+            // the first plain declarator names the type itself
+            let main = declarators.iter().find(|d| d.len() == 1 && d[0].is_word());
+            let name = match (main, &tag) {
+                (Some(d), _) => d[0].text.clone(),
+                (None, Some(tag)) => tag.clone(),
+                (None, None) => return out,
+            };
+            out.push(TypeDecl {
+                name: name.clone(),
+                kind,
+                tag: if main.is_some() { tag.clone() } else { None },
+                underlying: None,
+                is_typedef: main.is_some(),
+                fields,
+                values,
+                line: first.line,
+                doc_comment: doc.clone(),
+            });
+            // remaining declarators alias the same type: `*t_list_ptr`
+            let keyword = &stmt[spec].text;
+            let target = format!("{} {}", keyword, tag.as_deref().unwrap_or(&name));
+            for d in declarators.iter().filter(|d| !(d.len() == 1 && d[0].text == name)) {
+                let Some(param) = declarator(&target, d) else { continue };
+                out.push(TypeDecl {
+                    name: param.name,
+                    kind: TypeDeclKind::Typedef,
+                    tag: None,
+                    underlying: Some(param.type_name),
+                    is_typedef: true,
+                    fields: Vec::new(),
+                    values: Vec::new(),
+                    line: first.line,
+                    doc_comment: doc.clone(),
+                });
+            }
+        }
+        _ if is_typedef => {
+            let rest = strip_attributes(&stmt[1..]);
+            for d in split_commas(&rest) {
+                let param = parse_param(d, false);
+                if param.name.is_empty() {
+                    continue;
+                }
+                out.push(TypeDecl {
+                    name: param.name,
+                    kind: TypeDeclKind::Typedef,
+                    tag: None,
+                    underlying: Some(param.type_name),
+                    is_typedef: true,
+                    fields: Vec::new(),
+                    values: Vec::new(),
+                    line: first.line,
+                    doc_comment: doc.clone(),
+                });
+            }
+        }
+        _ => {}
+    }
+    out
+}
+
+fn parse_fields(code: &[&Token], leading: &[Option<String>], from: usize, to: usize) -> Vec<FieldDecl> {
+    let mut fields = Vec::new();
+    let mut start = from;
+    let mut i = from;
+    while i < to {
+        if code[i].is("{") || code[i].is("(") || code[i].is("[") {
+            i = matching(code, i) + 1;
+            continue;
+        }
+        if code[i].is(";") {
+            let seg = &code[start..i];
+            let doc = leading.get(start).cloned().flatten();
+            // drop bit-field widths: `unsigned int flag : 1`
+            let seg = match seg.iter().position(|t| t.is(":")) {
+                Some(p) => &seg[..p],
+                None => seg,
+            };
+            let params = match seg.iter().position(|t| t.is("{")) {
+                // nested aggregates are shown collapsed: `struct {...} pos`
+                Some(open) => {
+                    let close = matching(seg, open);
+                    let base = format!("{} {{...}}", join_tokens(&seg[..open]));
+                    split_commas(&seg[close + 1..])
+                        .into_iter()
+                        .filter_map(|d| declarator(&base, d))
+                        .collect()
+                }
+                None => split_declarators(seg),
+            };
+            fields.extend(params.into_iter().map(|p| FieldDecl {
+                name: p.name,
+                type_name: p.type_name,
+                doc_comment: doc.clone(),
+            }));
+            start = i + 1;
+        }
+        i += 1;
+    }
+    fields
+}
+
+/// Name declared by `d` on top of `base`: (`struct s_list`, `*t_plist`)
+/// gives `t_plist` of type `struct s_list *`.
+fn declarator(base: &str, d: &[&Token]) -> Option<CParam> {
+    let k = d.iter().rposition(|t| t.is_word())?;
+    let mut rest: Vec<&Token> = d[..k].to_vec();
+    rest.extend_from_slice(&d[k + 1..]);
+    let suffix = join_tokens(&rest);
+    let type_name = match suffix.as_str() {
+        "" => base.to_string(),
+        s if s.starts_with('[') => format!("{}{}", base, s),
+        s => format!("{} {}", base, s),
+    };
+    Some(CParam { name: d[k].text.clone(), type_name })
+}
+
+/// `char *a, **b` -> (`char *`, a), (`char **`, b)
+fn split_declarators(seg: &[&Token]) -> Vec<CParam> {
+    let head_end = seg.iter().position(|t| t.is(",")).unwrap_or(seg.len());
+    let head = &seg[..head_end];
+    let split = match head
+        .iter()
+        .position(|t| t.is("*") || t.is("("))
+        .or_else(|| head.iter().rposition(|t| t.is_word()))
+    {
+        Some(p) if p > 0 => p,
+        _ => return Vec::new(),
+    };
+    let base = &seg[..split];
+    split_commas(&seg[split..])
+        .into_iter()
+        .map(|part| {
+            let mut tokens: Vec<&Token> = base.to_vec();
+            tokens.extend(part.iter());
+            parse_param(&tokens, false)
+        })
+        .filter(|p| !p.name.is_empty())
+        .collect()
+}
+
+fn parse_enum_values(code: &[&Token], leading: &[Option<String>], from: usize, to: usize) -> Vec<EnumValueDecl> {
+    let mut values = Vec::new();
+    let mut offset = from;
+    for part in split_commas(&code[from..to]) {
+        let doc = leading.get(offset).cloned().flatten();
+        offset += part.len() + 1;
+        let Some(name) = part.first().filter(|t| t.is_word()) else { continue };
+        let value = part.iter().position(|t| t.is("=")).map(|eq| join_tokens(&part[eq + 1..]));
+        values.push(EnumValueDecl { name: name.text.clone(), value, doc_comment: doc });
+    }
+    values
+}
+
+/// Every `#define` with a non-empty body (include guards are skipped).
+pub fn parse_macros(src: &str) -> Vec<MacroDecl> {
+    let tokens = tokenize(src);
+    let mut out = Vec::new();
+    let mut pending: Option<String> = None;
+    for t in &tokens {
+        if t.kind == TokenKind::Comment {
+            pending = Some(t.text.clone());
+            continue;
+        }
+        let doc = pending.take();
+        if t.kind != TokenKind::Directive {
+            continue;
+        }
+        let Some(rest) = t.text.trim_start_matches('#').trim_start().strip_prefix("define") else { continue };
+        if !rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let rest = rest.trim_start();
+        let name_len = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        let (name, after) = rest.split_at(name_len);
+        if name.is_empty() {
+            continue;
+        }
+        // function-like only when `(` touches the name
+        let (parameters, body) = match after.strip_prefix('(') {
+            Some(args) => {
+                let (args, body) = args.split_once(')').unwrap_or((args, ""));
+                let params = args.split(',').map(|a| a.trim().to_string()).filter(|a| !a.is_empty()).collect();
+                (Some(params), body)
+            }
+            None => (None, after),
+        };
+        let body = clean_macro_body(body);
+        if body.is_empty() && parameters.is_none() {
+            continue;
+        }
+        out.push(MacroDecl { name: name.to_string(), parameters, body, line: t.line, doc_comment: doc });
+    }
+    out
+}
+
+/// Every `#include` of `src`, in order; computed includes are skipped.
+pub fn parse_includes(src: &str) -> Vec<IncludeDecl> {
+    tokenize(src).iter()
+        .filter(|t| t.kind == TokenKind::Directive)
+        .filter_map(|t| {
+            let rest = directive(&t.text)?.strip_prefix("include")?.trim_start();
+            let (close, system) = match rest.chars().next()? {
+                '"' => ('"', false),
+                '<' => ('>', true),
+                _ => return None,
+            };
+            let path = &rest[1..rest[1..].find(close)? + 1];
+            Some(IncludeDecl { path: path.to_string(), system, line: t.line })
+        })
+        .collect()
+}
+
+/// The macro of an include guard wrapping the whole file (`#ifndef X` then
+/// `#define X` before any code), or `#pragma once`.
+pub fn include_guard(src: &str) -> Option<String> {
+    let tokens: Vec<Token> = tokenize(src).into_iter().filter(|t| t.kind != TokenKind::Comment).collect();
+    let directives: Vec<&str> = tokens.iter()
+        .filter(|t| t.kind == TokenKind::Directive)
+        .filter_map(|t| directive(&t.text))
+        .collect();
+    if directives.iter().any(|d| d.strip_prefix("pragma").is_some_and(|p| p.trim() == "once")) {
+        return Some("#pragma once".to_string());
+    }
+    let (first, second) = (tokens.first()?, tokens.get(1)?);
+    if first.kind != TokenKind::Directive || second.kind != TokenKind::Directive {
+        return None;
+    }
+    let tested = directive(&first.text)?;
+    let tested = match tested.strip_prefix("ifndef") {
+        Some(name) => name.trim(),
+        None => tested.strip_prefix("if")?.trim_start().strip_prefix('!')?.trim_start()
+            .strip_prefix("defined")?.trim().trim_start_matches('(').trim_end_matches(')').trim(),
+    };
+    // `#define LIBFT_H 1` counts too
+    let defined = directive(&second.text)?.strip_prefix("define")?.split_whitespace().next()?;
+    (!tested.is_empty() && defined == tested).then(|| tested.to_string())
+}
+
+// `# include <x>` -> `include <x>`
+fn directive(text: &str) -> Option<&str> {
+    Some(text.strip_prefix('#')?.trim())
+}
+
+// join continuation lines, drop comments and collapse whitespace
+fn clean_macro_body(body: &str) -> String {
+    let joined = body.replace("\\\r\n", " ").replace("\\\n", " ");
+    let b = joined.as_bytes();
+    let mut text = String::new();
+    let mut i = 0;
+    let mut quote = None;
+    while i < b.len() {
+        let c = b[i];
+        match quote {
+            Some(q) => {
+                if c == b'\\' {
+                    // the escaped character may be several bytes long
+                    text.push('\\');
+                    i += 1;
+                    if let Some(escaped) = joined[i..].chars().next() {
+                        text.push(escaped);
+                        i += escaped.len_utf8();
+                    }
+                    continue;
+                }
+                if c == q {
+                    quote = None;
+                }
+            }
+            None if c == b'"' || c == b'\'' => quote = Some(c),
+            None if c == b'/' && b.get(i + 1) == Some(&b'*') => {
+                let mut line = 0;
+                i = skip_block_comment(b, i, &mut line);
+                text.push(' ');
+                continue;
+            }
+            None if c == b'/' && b.get(i + 1) == Some(&b'/') => break,
+            None => {}
+        }
+        let ch = joined[i..].chars().next().unwrap_or(' ');
+        text.push(ch);
+        i += ch.len_utf8();
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macro_body_keeps_non_ascii_escapes() {
+        let macros = parse_macros("#define STR \"\\é\"\n#define CH '\\ß' /* c */\n");
+        assert_eq!(macros[0].body, "\"\\é\"");
+        assert_eq!(macros[1].body, "'\\ß'");
+    }
+
+    #[test]
+    fn tokenizer_keeps_comments_directives_and_lines() {
+        let tokens = tokenize("/* doc */\n#define X 1\nint\tf(char *s, ...)\n{\n\treturn (s[0] == '\\'' || *s == \"}\"[0]);\n}\n");
+        let kinds: Vec<(TokenKind, &str, usize)> = tokens.iter().map(|t| (t.kind, t.text.as_str(), t.line)).collect();
+        assert_eq!(kinds[0], (TokenKind::Comment, "/* doc */", 1));
+        assert_eq!(kinds[1], (TokenKind::Directive, "#define X 1", 2));
+        assert_eq!(kinds[2], (TokenKind::Ident, "int", 3));
+        assert!(kinds.contains(&(TokenKind::Punct, "...", 3)));
+        assert!(kinds.contains(&(TokenKind::Punct, "==", 5)));
+        assert!(kinds.contains(&(TokenKind::Char, "'\\''", 5)));
+        assert!(kinds.contains(&(TokenKind::Str, "\"}\"", 5)));
+        assert_eq!(kinds.last(), Some(&(TokenKind::Punct, "}", 6)));
+    }
+
+    #[test]
+    fn knr_definitions_take_their_parameter_types() {
+        let src = "int\tft_pick(n, s, t)\n\tint n;\n\tchar *s, **t;\n{\n\treturn (n ? *s : **t);\n}\n";
+        let funcs = parse_functions(src);
+        assert_eq!(funcs.len(), 1);
+        let func = &funcs[0];
+        assert!(func.is_definition);
+        assert_eq!(func.name, "ft_pick");
+        assert_eq!(func.to_string(), "int ft_pick(int n, char *s, char **t)");
+    }
+
+    #[test]
+    fn function_pointer_parameters_keep_their_signature() {
+        let src = "void\tft_lstiter(t_list *lst, void (*f)(void *));\n\
+                   t_list\t*ft_lstmap(t_list *lst, void *(*f)(void *), void (*del)(void *));\n";
+        let funcs = parse_functions(src);
+        assert_eq!(funcs.len(), 2);
+        assert!(!funcs[0].is_definition);
+        assert_eq!(funcs[0].parameters[1], CParam { name: "f".to_string(), type_name: "void (*)(void *)".to_string() });
+        assert_eq!(funcs[1].to_string(), "t_list *ft_lstmap(t_list *lst, void *(*f)(void *), void (*del)(void *))");
+    }
+
+    #[test]
+    fn bodies_are_found_when_the_name_starts_a_line() {
+        let src = "static int\nhelper(int x)\n{\n\treturn (ft_abs(x));\n}\n\nint\tft_one(int x) { return (helper(x)); }\n\nint\n\tft_two(void)\n{\n\treturn (ft_one(2));\n}\n";
+        let bodies = function_bodies(src);
+        let found: Vec<(&str, usize, usize, &[String])> = bodies.iter()
+            .map(|b| (b.name.as_str(), b.span.first_line, b.span.last_line, b.span.calls.as_slice()))
+            .collect();
+        assert_eq!(found, vec![
+            ("helper", 1, 5, &["ft_abs".to_string()][..]),
+            ("ft_one", 7, 7, &["helper".to_string()][..]),
+            ("ft_two", 9, 13, &["ft_one".to_string()][..]),
+        ]);
+        assert!(bodies[0].words.contains(&"return".to_string()));
+    }
+
+    #[test]
+    fn multi_line_macros_are_joined() {
+        let macros = parse_macros("/* larger */\n#define MAX(a, b) \\\n\t((a) > (b) \\\n\t? (a) : (b))\n#define ONE 1\n");
+        assert_eq!(macros.len(), 2);
+        assert_eq!(macros[0].name, "MAX");
+        assert_eq!(macros[0].parameters, Some(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(macros[0].body, "((a) > (b) ? (a) : (b))");
+        assert_eq!(macros[0].line, 2);
+        assert_eq!(macros[0].doc_comment.as_deref(), Some("/* larger */"));
+        assert_eq!(macros[1].line, 5);
+    }
+}
@@ -0,0 +1,187 @@
+//! What each source and header file parsed to in the previous build, so
+//! that unchanged files are not parsed again. Entries are keyed by path
+//! and content hash; the whole cache is dropped when its format or the
+//! settings a file's result depends on change. It lives outside the output
+//! directory so that it is never deployed with the site.
+
+use crate::parser::ParsedFile;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where caches go unless `output.cache_dir` or `--cache-dir` says otherwise,
+/// relative to the working directory.
+pub const DEFAULT_DIR: &str = "target/libft-docs";
+
+// Bump whenever a file would parse to something else: parser fixes,
+// `ParsedFile` fields, anything the results are computed from
+const CACHE_FORMAT: u32 = 1;
+
+#[derive(Default, Deserialize)]
+pub struct Cache {
+    format: u32,
+    settings: String,
+    files: BTreeMap<String, Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    hash: String,
+    parsed: ParsedFile,
+}
+
+// What `save` writes, borrowing the results about to be merged
+#[derive(Serialize)]
+struct Saved<'a> {
+    format: u32,
+    settings: &'a str,
+    files: BTreeMap<&'a str, SavedEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct SavedEntry<'a> {
+    hash: &'a str,
+    parsed: &'a ParsedFile,
+}
+
+/// A file's entry, if any.
+pub enum Lookup {
+    Hit(Box<ParsedFile>),
+    Changed,
+    New,
+}
+
+/// The cache file of the sources in `source`, one per source tree so that
+/// builds of different trees do not drop each other's.
+pub fn file(dir: &Path, source: &Path) -> PathBuf {
+    let source = source.canonicalize().unwrap_or_else(|_| source.to_path_buf());
+    dir.join(format!("cache-{}.json", &hash(&source.to_string_lossy())[..16]))
+}
+
+/// Hex SHA-256 of a file's content.
+pub fn hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl Cache {
+    /// The cache at `path`, or an empty one when it is missing, unreadable
+    /// or was written in another format or with other `settings`.
+    pub fn load(path: &Path, settings: &str) -> Self {
+        let cache: Option<Cache> = fs::read_to_string(path).ok()
+            .and_then(|text| serde_json::from_str(&text).ok());
+        match cache {
+            Some(cache) if cache.format == CACHE_FORMAT && cache.settings == settings => cache,
+            _ => Self::default(),
+        }
+    }
+
+    /// Take the result stored for `path` if the content still hashes to `hash`.
+    pub fn take(&mut self, path: &str, hash: &str) -> Lookup {
+        match self.files.remove(path) {
+            Some(entry) if entry.hash == hash => Lookup::Hit(Box::new(entry.parsed)),
+            Some(_) => Lookup::Changed,
+            None => Lookup::New,
+        }
+    }
+
+    /// Paths not taken: the files removed since the previous build.
+    pub fn remaining(&self) -> impl Iterator<Item = &String> {
+        self.files.keys()
+    }
+}
+
+/// Replace the cache at `path` with `files` (path, hash and result).
+pub fn save<'a>(
+    path: &Path,
+    settings: &str,
+    files: impl Iterator<Item = (&'a str, &'a str, &'a ParsedFile)>,
+) -> anyhow::Result<()> {
+    let saved = Saved {
+        format: CACHE_FORMAT,
+        settings,
+        files: files.map(|(file, hash, parsed)| (file, SavedEntry { hash, parsed })).collect(),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(&saved)?)?;
+    Ok(())
+}
+
+/// Which files a build had to parse again.
+#[derive(Debug, Default)]
+pub struct Changes {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+}
+
+impl Changes {
+    pub fn report(&self) {
+        if self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty() {
+            println!("♻️  No source changes, {} file(s) reused from the cache", self.unchanged);
+            return;
+        }
+        for (icon, files) in [("➕", &self.added), ("✏️ ", &self.changed), ("➖", &self.removed)] {
+            for file in files {
+                println!("  {} {}", icon, file);
+            }
+        }
+        println!("♻️  {} added, {} changed, {} removed, {} reused from the cache",
+            self.added.len(), self.changed.len(), self.removed.len(), self.unchanged);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libft-docs-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("cache.json")
+    }
+
+    fn saved(path: &Path, settings: &str) {
+        let parsed = ParsedFile::default();
+        let (a, b) = (hash("int a;"), hash("int b;"));
+        let files = [("str/a.c", a.as_str(), &parsed), ("str/b.c", b.as_str(), &parsed)];
+        save(path, settings, files.into_iter()).unwrap();
+    }
+
+    #[test]
+    fn entries_hit_change_or_remain_as_removed() {
+        let path = temp_file("lookup");
+        saved(&path, "s");
+        let mut cache = Cache::load(&path, "s");
+        assert!(matches!(cache.take("str/a.c", &hash("int a;")), Lookup::Hit(_)));
+        assert!(matches!(cache.take("str/a.c", &hash("int a;")), Lookup::New));
+        assert!(matches!(cache.take("str/c.c", &hash("int c;")), Lookup::New));
+        let remaining: Vec<_> = cache.remaining().cloned().collect();
+        assert_eq!(remaining, ["str/b.c"]);
+        assert!(matches!(cache.take("str/b.c", &hash("int b = 1;")), Lookup::Changed));
+        assert_eq!(cache.remaining().count(), 0);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn other_settings_or_a_broken_file_drop_the_cache() {
+        let path = temp_file("settings");
+        saved(&path, "s");
+        assert_eq!(Cache::load(&path, "t").remaining().count(), 0);
+        fs::write(&path, "{").unwrap();
+        assert_eq!(Cache::load(&path, "s").remaining().count(), 0);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(Cache::load(&path, "s").remaining().count(), 0);
+    }
+
+    #[test]
+    fn each_source_tree_has_its_own_file() {
+        let dir = Path::new("target/libft-docs");
+        assert_ne!(file(dir, Path::new("/nonexistent/a")), file(dir, Path::new("/nonexistent/b")));
+        assert_eq!(file(dir, Path::new("/nonexistent/a")), file(dir, Path::new("/nonexistent/a")));
+    }
+}
@@ -0,0 +1,282 @@
+//! Who calls whom among the documented functions, from the calls found in
+//! their bodies: `calls`/`called_by` on every function, `see_also`
+//! suggestions from name families (`ft_str*`), the DOT and JSON exports and
+//! the small graph drawn on each function page.
+
+use crate::{escape, renderer, FunctionMetadata, LibraryMetadata};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+// Suggested `see_also` entries per function
+const MAX_SIBLINGS: usize = 5;
+
+/// Keep only calls to documented functions, fill in `called_by`, and
+/// suggest `see_also` (name siblings) and `related` (callers and callees)
+/// where the docs give none. A call goes to the `static` helper of the
+/// caller's own file when there is one, else to the public function.
+pub fn link(metadata: &mut LibraryMetadata) {
+    let public: HashSet<String> = metadata.functions.keys().cloned().collect();
+    let statics: HashSet<Callee> = metadata.internal_functions.iter().map(callee_key).collect();
+    let mut called_by: BTreeMap<Callee, Vec<String>> = BTreeMap::new();
+    for func in metadata.functions.values_mut().chain(metadata.internal_functions.iter_mut()) {
+        let file = func.source_file.clone();
+        let name = func.name.clone();
+        func.calls.retain(|c| *c != name && (public.contains(c) || statics.contains(&(file.clone(), c.clone()))));
+        for callee in &func.calls {
+            let local = (file.clone(), callee.clone());
+            let key = if statics.contains(&local) { local } else { (None, callee.clone()) };
+            called_by.entry(key).or_default().push(name.clone());
+        }
+    }
+
+    let public: Vec<String> = public.into_iter().collect();
+    for func in metadata.functions.values_mut().chain(metadata.internal_functions.iter_mut()) {
+        func.called_by = called_by.remove(&callee_key(func)).unwrap_or_default();
+        func.called_by.sort();
+        func.called_by.dedup();
+        if func.see_also.is_empty() {
+            func.see_also = siblings(&func.name, &public);
+        }
+        if func.related.is_empty() {
+            let mut related: Vec<String> = func.calls.iter().chain(&func.called_by).cloned().collect();
+            related.sort();
+            related.dedup();
+            func.related = related;
+        }
+    }
+}
+
+// A called function: its file for `static` helpers, which only their own
+// translation unit sees, and its name
+type Callee = (Option<String>, String);
+
+fn callee_key(func: &FunctionMetadata) -> Callee {
+    (func.source_file.clone().filter(|_| func.internal), func.name.clone())
+}
+
+// `ft_strdup` → `ft_strlen`, `ft_strchr`, ...: the longest shared prefix
+// first, at least two letters past `ft_`
+fn siblings(name: &str, names: &[String]) -> Vec<String> {
+    let Some(stem) = name.strip_prefix("ft_") else { return Vec::new() };
+    let mut out: Vec<(usize, &String)> = names.iter()
+        .filter(|other| other.as_str() != name)
+        .filter_map(|other| {
+            let shared = other.strip_prefix("ft_")?
+                .chars().zip(stem.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            (shared >= 2).then_some((shared, other))
+        })
+        .collect();
+    out.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    out.into_iter().take(MAX_SIBLINGS).map(|(_, n)| n.clone()).collect()
+}
+
+/// The graph of the listed functions, as written to `call-graph.json`.
+#[derive(Debug, Serialize)]
+pub struct CallGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Node {
+    pub name: String,
+    pub category: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+}
+
+/// Nodes sorted by name, edges by caller then callee.
+pub fn graph(metadata: &LibraryMetadata, include_internal: bool) -> CallGraph {
+    let mut functions = renderer::listed_functions(metadata, include_internal);
+    functions.sort_by(|a, b| a.name.cmp(&b.name));
+    let listed: HashSet<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+    let mut edges = Vec::new();
+    for func in &functions {
+        let mut calls: Vec<&String> = func.calls.iter().filter(|c| listed.contains(c.as_str())).collect();
+        calls.sort();
+        edges.extend(calls.into_iter().map(|to| Edge { from: func.name.clone(), to: to.clone() }));
+    }
+    let nodes = functions.iter()
+        .map(|f| Node {
+            name: f.name.clone(),
+            category: if f.category_path.is_empty() { f.category.clone() } else { f.category_path.clone() },
+        })
+        .collect();
+    CallGraph { nodes, edges }
+}
+
+impl CallGraph {
+    /// Graphviz source, one cluster per category.
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut clusters: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for node in &self.nodes {
+            clusters.entry(node.category.as_str()).or_default().push(node.name.as_str());
+        }
+        let mut out = String::from("digraph libft {\n    rankdir=LR;\n    node [shape=box, fontname=\"monospace\"];\n");
+        for (i, (category, names)) in clusters.iter().enumerate() {
+            out.push_str(&format!("    subgraph cluster_{} {{\n        label={};\n", i, quote(category)));
+            for name in names {
+                out.push_str(&format!("        {};\n", quote(name)));
+            }
+            out.push_str("    }\n");
+        }
+        for edge in &self.edges {
+            out.push_str(&format!("    {} -> {};\n", quote(&edge.from), quote(&edge.to)));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+// Layout of the per-function graph: callers, the function, callees
+pub(crate) const NODE_WIDTH: usize = 180;
+pub(crate) const NODE_HEIGHT: usize = 26;
+pub(crate) const ROW_GAP: usize = 10;
+const COLUMNS: [usize; 3] = [4, 234, 464];
+const WIDTH: usize = 648;
+const MAX_LABEL: usize = 22;
+
+/// A function in the drawn graph; `href` is `None` for functions without a
+/// page (unlisted `static` helpers).
+pub struct GraphLink {
+    pub name: String,
+    pub href: Option<String>,
+}
+
+/// An SVG with the callers of `name` on the left and its callees on the
+/// right, each linking to its page. `id` keeps the arrow marker unique on
+/// the single-page site.
+pub fn neighbourhood_svg(id: &str, name: &str, callers: &[GraphLink], callees: &[GraphLink]) -> String {
+    let rows = callers.len().max(callees.len()).max(1);
+    let pitch = NODE_HEIGHT + ROW_GAP;
+    let height = rows * pitch - ROW_GAP + 8;
+    let top = |count: usize, i: usize| 4 + (rows - count) * pitch / 2 + i * pitch;
+    let center = top(1, 0);
+    let marker = format!("{}-arrow", id);
+
+    let mut out = format!(
+        "<svg class=\"call-graph__svg\" viewBox=\"0 0 {} {}\" role=\"img\" aria-label=\"Call graph of {}\">\
+         <defs><marker id=\"{}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+         <path d=\"M0,0 L10,5 L0,10 z\" class=\"call-graph__arrow\"/></marker></defs>",
+        WIDTH, height, escape::html(name), marker);
+    let edge = |x1: usize, y1: usize, x2: usize, y2: usize| format!(
+        "<line class=\"call-graph__edge\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" marker-end=\"url(#{})\"/>",
+        x1, y1 + NODE_HEIGHT / 2, x2, y2 + NODE_HEIGHT / 2, marker);
+    for (i, _) in callers.iter().enumerate() {
+        out.push_str(&edge(COLUMNS[0] + NODE_WIDTH, top(callers.len(), i), COLUMNS[1], center));
+    }
+    for (i, _) in callees.iter().enumerate() {
+        out.push_str(&edge(COLUMNS[1] + NODE_WIDTH, center, COLUMNS[2], top(callees.len(), i)));
+    }
+    for (i, link) in callers.iter().enumerate() {
+        out.push_str(&node(link, COLUMNS[0], top(callers.len(), i), false));
+    }
+    out.push_str(&node(&GraphLink { name: name.to_string(), href: None }, COLUMNS[1], center, true));
+    for (i, link) in callees.iter().enumerate() {
+        out.push_str(&node(link, COLUMNS[2], top(callees.len(), i), false));
+    }
+    out.push_str("</svg>");
+    out
+}
+
+// one box, also drawn by `headers`
+pub(crate) fn node(link: &GraphLink, x: usize, y: usize, current: bool) -> String {
+    let label = if link.name.chars().count() > MAX_LABEL {
+        format!("{}…", link.name.chars().take(MAX_LABEL - 1).collect::<String>())
+    } else {
+        link.name.clone()
+    };
+    let class = if current { "call-graph__node call-graph__node--current" } else { "call-graph__node" };
+    let shape = format!(
+        "<g class=\"{}\"><title>{}</title><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\"/>\
+         <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text></g>",
+        class, escape::html(&link.name), x, y, NODE_WIDTH, NODE_HEIGHT,
+        x + NODE_WIDTH / 2, y + NODE_HEIGHT / 2 + 4, escape::html(&label));
+    match &link.href {
+        Some(href) => format!("<a href=\"{}\">{}</a>", escape::html(href), shape),
+        None => shape,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn function(name: &str, file: &str, calls: &[&str], internal: bool) -> FunctionMetadata {
+        serde_json::from_value(json!({
+            "name": name, "category": "str", "source_file": file, "calls": calls, "internal": internal,
+        })).unwrap()
+    }
+
+    fn library(functions: Vec<FunctionMetadata>) -> LibraryMetadata {
+        let mut metadata: LibraryMetadata = serde_json::from_value(json!({
+            "name": "libft", "version": "1", "description": "", "author": "", "categories": ["str"], "functions": {},
+        })).unwrap();
+        for func in functions {
+            if func.internal {
+                metadata.internal_functions.push(func);
+            } else {
+                metadata.functions.insert(func.name.clone(), func);
+            }
+        }
+        metadata
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn siblings_rank_by_shared_prefix_then_name() {
+        let all = names(&["ft_strdup", "ft_strlen", "ft_strlcpy", "ft_strncmp", "ft_memcpy", "ft_putstr"]);
+        assert_eq!(siblings("ft_strlcat", &all), ["ft_strlcpy", "ft_strlen", "ft_strdup", "ft_strncmp"]);
+        assert_eq!(siblings("ft_memset", &all), ["ft_memcpy"]);
+        assert!(siblings("ft_bzero", &all).is_empty());
+        assert!(siblings("strlen", &all).is_empty());
+    }
+
+    #[test]
+    fn siblings_are_capped() {
+        let all: Vec<String> = (0..10).map(|i| format!("ft_lst{}", i)).collect();
+        assert_eq!(siblings("ft_lstnew", &all).len(), MAX_SIBLINGS);
+    }
+
+    #[test]
+    fn calls_go_to_the_helper_of_the_callers_file() {
+        let mut metadata = library(vec![
+            function("ft_a", "str/ft_a.c", &["helper", "ft_b", "printf"], false),
+            function("ft_b", "str/ft_b.c", &["helper"], false),
+            function("helper", "str/ft_a.c", &[], true),
+            function("helper", "str/ft_b.c", &[], true),
+        ]);
+        link(&mut metadata);
+        assert_eq!(metadata.functions["ft_a"].calls, ["helper", "ft_b"]);
+        assert_eq!(metadata.functions["ft_b"].called_by, ["ft_a"]);
+        assert_eq!(metadata.functions["ft_a"].related, ["ft_b", "helper"]);
+        let callers: Vec<_> = metadata.internal_functions.iter().map(|f| f.called_by.clone()).collect();
+        assert_eq!(callers, [names(&["ft_a"]), names(&["ft_b"])]);
+    }
+
+    #[test]
+    fn dot_has_one_cluster_per_category() {
+        let graph = CallGraph {
+            nodes: vec![
+                Node { name: "ft_a".into(), category: "str".into() },
+                Node { name: "ft_b".into(), category: "mem".into() },
+            ],
+            edges: vec![Edge { from: "ft_a".into(), to: "ft_b".into() }],
+        };
+        let dot = graph.to_dot();
+        assert!(dot.contains("subgraph cluster_0 {\n        label=\"mem\";\n        \"ft_b\";\n    }"));
+        assert!(dot.contains("subgraph cluster_1 {\n        label=\"str\";\n        \"ft_a\";\n    }"));
+        assert!(dot.contains("    \"ft_a\" -> \"ft_b\";\n"));
+    }
+}
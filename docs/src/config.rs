@@ -0,0 +1,368 @@
+//! `docs.toml`: who the library is, which files document it, extra tag
+//! rules, output options and the dev server port. Every setting has a
+//! default matching libft, so the file is optional; command-line flags
+//! override it.
+//!
+//! ```toml
+//! [project]
+//! name = "libft"
+//! version = "1.0.0"
+//!
+//! [source]
+//! dir = "."
+//! roots = ["libft"]
+//! exclude = ["**/main.c", "**/vendor/**"]
+//! manual_dirs = ["docs/man"]
+//!
+//! [[tags.rule]]
+//! tag = "parsing"
+//! names = ["ft_parse*", "ft_ato*"]
+//! body = ["strtol"]
+//!
+//! [[tags.group]]
+//! name = "difficulty"
+//! tags = ["basic", "intermediate", "advanced", "expert"]
+//! default = "basic"
+//!
+//! [output]
+//! dir = "dist"
+//! single_page = false
+//!
+//! [server]
+//! port = 3000
+//! ```
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Looked for in the working directory, then in the source directory.
+pub const CONFIG_FILE: &str = "docs.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub project: Project,
+    pub source: Source,
+    pub tags: Tags,
+    pub output: Output,
+    pub server: Server,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Project {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub author: String,
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self {
+            name: "libft".to_string(),
+            version: "1.0.0".to_string(),
+            description: "42 School C Library - Extended standard library functions".to_string(),
+            author: "dlesieur".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Source {
+    // the library's source tree, relative to the config file
+    pub dir: Option<String>,
+    // directories whose subdirectories are the categories, relative to
+    // `dir`; none means `libft/` when there is one, else `dir` itself
+    pub roots: Vec<String>,
+    // globs over paths relative to `dir`
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    // where manuals are looked for, relative to `dir`; none means the
+    // `docs/man`, `docs/api` and `docs` directories of the roots and of `dir`
+    pub manual_dirs: Vec<String>,
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            roots: Vec::new(),
+            include: vec!["**/*.c".to_string(), "**/*.h".to_string()],
+            exclude: [
+                "docs", "doc", "minilibx-linux", "target", "dist", "website", "bin",
+                "obj", "build", ".git", ".github", ".idea", ".vscode",
+            ].iter()
+                .map(|dir| format!("**/{}/**", dir))
+                .chain(std::iter::once("**/main.c".to_string()))
+                .collect(),
+            manual_dirs: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tags {
+    // keep the rules and groups of `tags/default.toml`
+    pub builtin: bool,
+    #[serde(rename = "rule")]
+    pub rules: Vec<TagRule>,
+    #[serde(rename = "group")]
+    pub groups: Vec<TagGroup>,
+}
+
+impl Default for Tags {
+    fn default() -> Self {
+        Self { builtin: true, rules: Vec::new(), groups: Vec::new() }
+    }
+}
+
+/// Adds `tag` to the functions matching every criterion it sets; within a
+/// criterion one pattern is enough. Patterns are globs.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagRule {
+    pub tag: String,
+    // wins over the lower priorities of its group
+    pub priority: i32,
+    pub names: Vec<String>,
+    // identifiers and keywords of the body, e.g. `for` or `pthread_*`
+    pub body: Vec<String>,
+    // the declaring header, relative to the source directory
+    pub headers: Vec<String>,
+    // the category path, e.g. `data_structures/*`
+    pub categories: Vec<String>,
+    // tags given by other rules (or the sources and manuals)
+    pub tags: Vec<String>,
+    // the body calls the function itself
+    pub recursive: bool,
+}
+
+/// Mutually exclusive tags, such as difficulty levels: a function keeps the
+/// one given by its manual, else by the rule of highest priority, else
+/// `default`. Each group is a section of the site's filter bar.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagGroup {
+    pub name: String,
+    pub label: Option<String>,
+    pub tags: Vec<String>,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Output {
+    // relative to the config file, like `theme`
+    pub dir: Option<String>,
+    pub single_page: bool,
+    pub offline: Option<bool>,
+    pub include_internal: bool,
+    pub theme: Option<String>,
+    pub strict: bool,
+    // where parse results are cached between builds, see `cache`
+    pub cache_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Server {
+    pub port: Option<u16>,
+}
+
+impl Config {
+    /// Read `explicit`, else the first `docs.toml` found in the working
+    /// directory or `source`, else use the defaults. Paths in the file are
+    /// made relative to the working directory.
+    pub fn load(explicit: Option<&str>, source: Option<&str>) -> anyhow::Result<Self> {
+        let path = match explicit {
+            Some(path) => Some(PathBuf::from(path)),
+            None => [Some(Path::new(".")), source.map(Path::new)].into_iter()
+                .flatten()
+                .map(|dir| dir.join(CONFIG_FILE))
+                .find(|p| p.is_file()),
+        };
+        let Some(path) = path else { return Ok(Self::default()) };
+        let text = fs::read_to_string(&path)
+            .map_err(|err| anyhow::anyhow!("cannot read {}: {}", path.display(), err))?;
+        let mut config: Config = toml::from_str(&text)
+            .map_err(|err| anyhow::anyhow!("{}: {}", path.display(), err.message()))?;
+        config.selection()?;
+        crate::tags::Tagger::new(&config.tags).map_err(|err| anyhow::anyhow!("{}: {}", path.display(), err))?;
+        let base = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let rebase = |p: &mut Option<String>| {
+            if let Some(rel) = p.as_mut().filter(|rel| Path::new(rel.as_str()).is_relative()) {
+                *rel = base.join(&*rel).to_string_lossy().to_string();
+            }
+        };
+        rebase(&mut config.source.dir);
+        rebase(&mut config.output.dir);
+        rebase(&mut config.output.theme);
+        rebase(&mut config.output.cache_dir);
+        println!("⚙️  Using {}", path.display());
+        Ok(config)
+    }
+
+    /// The include and exclude globs, compiled.
+    pub fn selection(&self) -> anyhow::Result<Selection> {
+        Ok(Selection { include: glob_set(&self.source.include)?, exclude: glob_set(&self.source.exclude)? })
+    }
+
+    /// The category roots below `source`.
+    pub fn roots(&self, source: &Path) -> Vec<PathBuf> {
+        if self.source.roots.is_empty() {
+            return vec![crate::parser::categories_root(source)];
+        }
+        self.source.roots.iter().map(|r| source.join(r)).collect()
+    }
+
+    /// Where manuals are looked for, most specific first.
+    pub fn manual_dirs(&self, source: &Path) -> Vec<PathBuf> {
+        if !self.source.manual_dirs.is_empty() {
+            return self.source.manual_dirs.iter().map(|d| source.join(d)).collect();
+        }
+        let mut dirs: Vec<PathBuf> = self.roots(source).iter()
+            .chain(std::iter::once(&source.to_path_buf()))
+            .flat_map(|dir| {
+                let docs = dir.join("docs");
+                [docs.join("man"), docs.join("api"), docs]
+            })
+            .collect();
+        // a root may be the source directory itself
+        let mut seen = HashSet::new();
+        dirs.retain(|d| seen.insert(d.clone()));
+        dirs
+    }
+}
+
+/// Which files under the source directory are documented.
+#[derive(Debug, Clone)]
+pub struct Selection {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl Selection {
+    /// `path` relative to the source directory.
+    pub fn selects(&self, path: &Path) -> bool {
+        self.include.is_match(path) && !self.exclude.is_match(path)
+    }
+
+    /// Whether an exclude glob covers everything below the directory `path`,
+    /// such as `**/minilibx-linux/**`.
+    pub fn excludes_dir(&self, path: &Path) -> bool {
+        self.exclude.is_match(path.join("*"))
+    }
+
+    /// The `.c` and `.h` files below `source` the globs select, test files
+    /// included, in path order. Excluded directories are not walked.
+    pub fn files(&self, source: &Path) -> Vec<PathBuf> {
+        WalkDir::new(source)
+            .follow_links(true)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| {
+                let rel = e.path().strip_prefix(source).unwrap_or(e.path());
+                !e.file_type().is_dir() || rel.as_os_str().is_empty() || !self.excludes_dir(rel)
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| p.extension().is_some_and(|x| x == "c" || x == "h"))
+            .filter(|p| self.selects(p.strip_prefix(source).unwrap_or(p)))
+            .collect()
+    }
+}
+
+pub(crate) fn glob_set(patterns: &[String]) -> anyhow::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).map_err(|err| anyhow::anyhow!("invalid glob `{}`: {}", pattern, err))?);
+    }
+    Ok(builder.build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libft-docs-config-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn default_selection_skips_vendored_and_entry_points() {
+        let selection = Config::default().selection().unwrap();
+        assert!(selection.selects(Path::new("str/ft_strlen.c")));
+        assert!(selection.selects(Path::new("include/libft.h")));
+        assert!(!selection.selects(Path::new("str/ft_strlen.o")));
+        assert!(!selection.selects(Path::new("tools/main.c")));
+        assert!(!selection.selects(Path::new("minilibx-linux/mlx.h")));
+        assert!(selection.excludes_dir(Path::new("minilibx-linux")));
+        assert!(selection.excludes_dir(Path::new("libft/.git")));
+        assert!(!selection.excludes_dir(Path::new("str")));
+    }
+
+    #[test]
+    fn files_are_walked_in_path_order_without_excluded_dirs() {
+        let dir = temp_dir("files");
+        for file in ["str/ft_b.c", "str/ft_a.c", "libft.h", "docs/example.c", "str/notes.txt"] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let files: Vec<_> = Config::default().selection().unwrap().files(&dir).into_iter()
+            .map(|p| p.strip_prefix(&dir).unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(files, ["libft.h", "str/ft_a.c", "str/ft_b.c"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn paths_are_made_relative_to_the_config_file() {
+        let dir = temp_dir("load");
+        let path = dir.join(CONFIG_FILE);
+        fs::write(&path, "[source]\ndir = \"lib\"\n[output]\ndir = \"/srv/site\"\nsingle_page = true\n").unwrap();
+        let config = Config::load(Some(path.to_str().unwrap()), None).unwrap();
+        assert_eq!(config.source.dir, Some(dir.join("lib").to_string_lossy().to_string()));
+        assert_eq!(config.output.dir.as_deref(), Some("/srv/site"));
+        assert!(config.output.single_page);
+        assert_eq!(config.project.name, "libft");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn mistakes_are_reported_with_the_file() {
+        let dir = temp_dir("invalid");
+        let path = dir.join(CONFIG_FILE);
+        fs::write(&path, "[output]\nsingle_pag = true\n").unwrap();
+        let err = Config::load(Some(path.to_str().unwrap()), None).unwrap_err().to_string();
+        assert!(err.starts_with(&path.display().to_string()) && err.contains("single_pag"), "{}", err);
+        fs::write(&path, "[source]\nexclude = [\"[\"]\n").unwrap();
+        let err = Config::load(Some(path.to_str().unwrap()), None).unwrap_err().to_string();
+        assert!(err.contains("invalid glob `[`"), "{}", err);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn manual_dirs_default_to_the_docs_of_roots_and_source() {
+        let mut config = Config::default();
+        config.source.roots = vec!["libft".to_string(), ".".to_string()];
+        let dirs = config.manual_dirs(Path::new("src"));
+        assert_eq!(dirs, [
+            Path::new("src/libft/docs/man"), Path::new("src/libft/docs/api"), Path::new("src/libft/docs"),
+            Path::new("src/docs/man"), Path::new("src/docs/api"), Path::new("src/docs"),
+        ]);
+        config.source.manual_dirs = vec!["man".to_string()];
+        assert_eq!(config.manual_dirs(Path::new("src")), [Path::new("src/man")]);
+    }
+}
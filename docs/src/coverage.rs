@@ -0,0 +1,296 @@
+//! Documentation coverage: which functions still carry the generator's
+//! placeholders, per function and per category, as a table, JSON or JUnit.
+//! Functions no test calls are flagged too, outside the score.
+
+use crate::{escape, renderer, FunctionMetadata, LibraryMetadata, NO_DESCRIPTION, NO_RETURN_VALUE, PLACEHOLDER_EXAMPLE};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The documentation aspects measured for every function.
+pub const ASPECTS: [&str; 5] = ["description", "params", "return", "examples", "manual"];
+
+#[derive(Debug, Serialize)]
+pub struct FunctionCoverage {
+    pub name: String,
+    pub category: String,
+    pub source_file: Option<String>,
+    pub line: Option<usize>,
+    pub description: bool,
+    // every parameter described; trivially true without parameters
+    pub params: bool,
+    // trivially true for `void` functions
+    #[serde(rename = "return")]
+    pub return_value: bool,
+    pub examples: bool,
+    pub manual: bool,
+    // called from the test suite; not part of the score
+    pub tested: bool,
+    pub percent: f64,
+}
+
+impl FunctionCoverage {
+    fn aspects(&self) -> [bool; 5] {
+        [self.description, self.params, self.return_value, self.examples, self.manual]
+    }
+
+    /// Names of the aspects still undocumented.
+    pub fn missing(&self) -> Vec<&'static str> {
+        ASPECTS.iter().zip(self.aspects()).filter(|(_, ok)| !ok).map(|(a, _)| *a).collect()
+    }
+
+    fn location(&self) -> String {
+        match (&self.source_file, self.line) {
+            (Some(file), Some(line)) => format!("{}:{}", file, line),
+            (Some(file), None) => file.clone(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Documented functions per aspect for one category (or the whole library).
+#[derive(Debug, Default, Serialize)]
+pub struct CategoryCoverage {
+    pub name: String,
+    pub functions: usize,
+    pub description: usize,
+    pub params: usize,
+    #[serde(rename = "return")]
+    pub return_value: usize,
+    pub examples: usize,
+    pub manual: usize,
+    pub tested: usize,
+    pub percent: f64,
+}
+
+impl CategoryCoverage {
+    fn add(&mut self, f: &FunctionCoverage) {
+        self.functions += 1;
+        self.description += f.description as usize;
+        self.params += f.params as usize;
+        self.return_value += f.return_value as usize;
+        self.examples += f.examples as usize;
+        self.manual += f.manual as usize;
+        self.tested += f.tested as usize;
+        let documented = self.description + self.params + self.return_value + self.examples + self.manual;
+        self.percent = percent(documented, self.functions * ASPECTS.len());
+    }
+
+    fn counts(&self) -> [usize; 5] {
+        [self.description, self.params, self.return_value, self.examples, self.manual]
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoverageReport {
+    pub functions: Vec<FunctionCoverage>,
+    pub categories: Vec<CategoryCoverage>,
+    pub total: CategoryCoverage,
+}
+
+/// Measure every listed function, sorted by category then name.
+pub fn compute(metadata: &LibraryMetadata, include_internal: bool) -> CoverageReport {
+    let mut functions: Vec<FunctionCoverage> = renderer::listed_functions(metadata, include_internal)
+        .into_iter()
+        .map(function_coverage)
+        .collect();
+    functions.sort_by(|a, b| a.category.cmp(&b.category).then_with(|| a.name.cmp(&b.name)));
+
+    let mut categories: BTreeMap<String, CategoryCoverage> = BTreeMap::new();
+    let mut total = CategoryCoverage { name: "total".to_string(), ..Default::default() };
+    for f in &functions {
+        categories.entry(f.category.clone())
+            .or_insert_with(|| CategoryCoverage { name: f.category.clone(), ..Default::default() })
+            .add(f);
+        total.add(f);
+    }
+    if functions.is_empty() {
+        total.percent = 100.0;
+    }
+
+    CoverageReport { functions, categories: categories.into_values().collect(), total }
+}
+
+fn function_coverage(func: &FunctionMetadata) -> FunctionCoverage {
+    let mut coverage = FunctionCoverage {
+        name: func.name.clone(),
+        category: if func.category_path.is_empty() { func.category.clone() } else { func.category_path.clone() },
+        source_file: func.source_file.clone(),
+        line: func.line,
+        description: documented(&func.description, NO_DESCRIPTION),
+        params: func.parameters.iter().all(|p| !p.description.trim().is_empty()),
+        return_value: returns_void(func) || documented(&func.return_value, NO_RETURN_VALUE),
+        examples: func.examples.iter().any(|e| !e.code.trim().is_empty() && !e.code.contains(PLACEHOLDER_EXAMPLE)),
+        manual: func.has_manual,
+        tested: func.examples.iter().any(|e| e.source_file.is_some()),
+        percent: 0.0,
+    };
+    let aspects = coverage.aspects();
+    coverage.percent = percent(aspects.iter().filter(|ok| **ok).count(), aspects.len());
+    coverage
+}
+
+fn documented(text: &str, placeholder: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty() && text != placeholder
+}
+
+// `void ft_bzero(...)`, not `void *ft_memset(...)`
+fn returns_void(func: &FunctionMetadata) -> bool {
+    let Some(end) = func.prototype.find(&format!("{}(", func.name)).or_else(|| func.prototype.find(&func.name)) else {
+        return false;
+    };
+    let words: Vec<&str> = func.prototype[..end].split_whitespace()
+        .filter(|w| !matches!(*w, "static" | "inline" | "extern"))
+        .collect();
+    words == ["void"]
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 { 100.0 } else { (part as f64 * 1000.0 / whole as f64).round() / 10.0 }
+}
+
+impl CoverageReport {
+    /// Per-function and per-category tables for the terminal.
+    pub fn table(&self) -> String {
+        let name_width = self.functions.iter().map(|f| f.name.len())
+            .chain(self.categories.iter().map(|c| c.name.len()))
+            .chain(["Function".len()])
+            .max()
+            .unwrap_or(8);
+
+        let mut out = String::new();
+        out.push_str(&format!("{:<name_width$}  {:>11}  {:>6}  {:>6}  {:>8}  {:>6}  {:>6}  {:>5}\n",
+            "Function", "Description", "Params", "Return", "Examples", "Manual", "Score", "Tests"));
+        let mut category = None;
+        for f in &self.functions {
+            if category != Some(&f.category) {
+                category = Some(&f.category);
+                out.push_str(&format!("── {}\n", f.category));
+            }
+            let mark = |ok: bool| if ok { "✓" } else { "✗" };
+            out.push_str(&format!("{:<name_width$}  {:>11}  {:>6}  {:>6}  {:>8}  {:>6}  {:>5.1}%  {:>5}\n",
+                f.name, mark(f.description), mark(f.params), mark(f.return_value), mark(f.examples), mark(f.manual),
+                f.percent, mark(f.tested)));
+        }
+
+        out.push('\n');
+        out.push_str(&format!("{:<name_width$}  {:>9}  {:>11}  {:>6}  {:>6}  {:>8}  {:>6}  {:>6}  {:>6}\n",
+            "Category", "Functions", "Description", "Params", "Return", "Examples", "Manual", "Score", "Tests"));
+        for c in self.categories.iter().chain([&self.total]) {
+            let [description, params, return_value, examples, manual] = c.counts()
+                .map(|n| format!("{:.1}%", percent(n, c.functions)));
+            out.push_str(&format!("{:<name_width$}  {:>9}  {:>11}  {:>6}  {:>6}  {:>8}  {:>6}  {:>5.1}%  {:>6}\n",
+                c.name, c.functions, description, params, return_value, examples, manual, c.percent,
+                format!("{:.1}%", percent(c.tested, c.functions))));
+        }
+        out
+    }
+
+    /// Functions no test calls, in table order.
+    pub fn untested(&self) -> Vec<&str> {
+        self.functions.iter().filter(|f| !f.tested).map(|f| f.name.as_str()).collect()
+    }
+
+    /// One test suite per category, one test case per function; a function
+    /// fails when its own score is below `threshold`.
+    pub fn junit(&self, threshold: f64) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let failures = self.functions.iter().filter(|f| f.percent < threshold).count();
+        out.push_str(&format!("<testsuites name=\"documentation coverage\" tests=\"{}\" failures=\"{}\">\n",
+            self.functions.len(), failures));
+        for c in &self.categories {
+            let funcs: Vec<&FunctionCoverage> = self.functions.iter().filter(|f| f.category == c.name).collect();
+            let failures = funcs.iter().filter(|f| f.percent < threshold).count();
+            out.push_str(&format!("  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+                escape::html(&c.name), funcs.len(), failures));
+            for f in funcs {
+                let location = f.location();
+                let file = if location.is_empty() { String::new() } else { format!(" file=\"{}\"", escape::html(&location)) };
+                out.push_str(&format!("    <testcase classname=\"{}\" name=\"{}\"{}",
+                    escape::html(&c.name), escape::html(&f.name), file));
+                if f.percent < threshold {
+                    out.push_str(&format!(">\n      <failure message=\"{:.1}% documented, below {:.1}%\">missing: {}</failure>\n    </testcase>\n",
+                        f.percent, threshold, f.missing().join(", ")));
+                } else {
+                    out.push_str("/>\n");
+                }
+            }
+            out.push_str("  </testsuite>\n");
+        }
+        out.push_str("</testsuites>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn function(value: serde_json::Value) -> FunctionMetadata {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn report() -> CoverageReport {
+        let mut metadata: LibraryMetadata = serde_json::from_value(json!({
+            "name": "libft", "version": "1", "description": "", "author": "", "categories": ["mem", "str"], "functions": {},
+        })).unwrap();
+        let mut documented = function(json!({
+            "name": "ft_bzero", "category": "mem", "prototype": "void\tft_bzero(void *s, size_t n)",
+            "description": "Zeroes n bytes.", "return_value": NO_RETURN_VALUE,
+            "parameters": [{ "name": "s", "type_name": "void *", "description": "the area" }],
+            "examples": [{ "title": "t", "code": "ft_bzero(b, 2);", "output": null, "source_file": "tests/mem.c" }],
+            "source_file": "mem/ft_bzero.c", "line": 3,
+        }));
+        documented.has_manual = true;
+        let bare = function(json!({
+            "name": "ft_strlen", "category": "str", "prototype": "size_t\tft_strlen(const char *s)",
+            "description": NO_DESCRIPTION, "return_value": NO_RETURN_VALUE,
+            "parameters": [{ "name": "s", "type_name": "const char *", "description": "" }],
+            "examples": [{ "title": "t", "code": PLACEHOLDER_EXAMPLE, "output": null }],
+        }));
+        metadata.functions.insert(documented.name.clone(), documented);
+        metadata.functions.insert(bare.name.clone(), bare);
+        compute(&metadata, false)
+    }
+
+    #[test]
+    fn placeholders_do_not_count_and_void_needs_no_return() {
+        let report = report();
+        let [bzero, strlen] = &report.functions[..] else { panic!("{:?}", report.functions) };
+        assert_eq!(bzero.name, "ft_bzero");
+        assert!(bzero.missing().is_empty());
+        assert_eq!(bzero.percent, 100.0);
+        assert!(bzero.tested);
+        assert_eq!(strlen.missing(), ASPECTS);
+        assert_eq!(strlen.percent, 0.0);
+        assert!(!strlen.tested);
+        assert_eq!(report.untested(), ["ft_strlen"]);
+    }
+
+    #[test]
+    fn categories_and_total_sum_their_functions() {
+        let report = report();
+        let names: Vec<_> = report.categories.iter().map(|c| (c.name.as_str(), c.functions, c.percent)).collect();
+        assert_eq!(names, [("mem", 1, 100.0), ("str", 1, 0.0)]);
+        assert_eq!(report.total.functions, 2);
+        assert_eq!(report.total.percent, 50.0);
+        assert_eq!(report.total.tested, 1);
+    }
+
+    #[test]
+    fn junit_fails_functions_below_the_threshold() {
+        let xml = report().junit(50.0);
+        assert!(xml.contains("<testsuites name=\"documentation coverage\" tests=\"2\" failures=\"1\">"));
+        assert!(xml.contains("<testcase classname=\"mem\" name=\"ft_bzero\" file=\"mem/ft_bzero.c:3\"/>"));
+        assert!(xml.contains("<failure message=\"0.0% documented, below 50.0%\">missing: description, params, return, examples, manual</failure>"));
+    }
+
+    #[test]
+    fn pointer_returns_are_not_void() {
+        let func = function(json!({ "name": "ft_memset", "category": "mem", "prototype": "void\t*ft_memset(void *b, int c, size_t len)" }));
+        assert!(!returns_void(&func));
+        let func = function(json!({ "name": "helper", "category": "mem", "prototype": "static void\thelper(void)" }));
+        assert!(returns_void(&func));
+    }
+}
@@ -0,0 +1,86 @@
+//! Problems found in the inputs, located by file, line and column.
+
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: PathBuf,
+    // 1-based
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(file: impl Into<PathBuf>, line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self { severity: Severity::Error, file: file.into(), line, column, message: message.into() }
+    }
+
+    pub fn warning(file: impl Into<PathBuf>, line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self { severity: Severity::Warning, file: file.into(), line, column, message: message.into() }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}:{}:{}: {}: {}", self.file.display(), self.line, self.column, level, self.message)
+    }
+}
+
+/// Print every diagnostic; fail on errors, and on warnings too when `strict`.
+pub fn report(diagnostics: &[Diagnostic], strict: bool) -> anyhow::Result<()> {
+    print(diagnostics);
+    let errors = diagnostics.iter().filter(|d| d.severity == Severity::Error).count();
+    let warnings = diagnostics.len() - errors;
+    if strict && !diagnostics.is_empty() {
+        anyhow::bail!("{} error(s) and {} warning(s) in strict mode", errors, warnings);
+    }
+    if errors > 0 {
+        anyhow::bail!("{} error(s) and {} warning(s)", errors, warnings);
+    }
+    Ok(())
+}
+
+/// Print every diagnostic, for callers with their own idea of failure.
+pub fn print(diagnostics: &[Diagnostic]) {
+    for d in diagnostics {
+        match d.severity {
+            Severity::Warning => eprintln!("⚠️  {}", d),
+            Severity::Error => eprintln!("❌ {}", d),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostics_read_like_compiler_messages() {
+        let d = Diagnostic::warning("docs/man/ft_a.json", 3, 5, "unknown property `x`");
+        assert_eq!(d.to_string(), "docs/man/ft_a.json:3:5: warning: unknown property `x`");
+    }
+
+    #[test]
+    fn errors_fail_and_warnings_only_when_strict() {
+        let warning = Diagnostic::warning("a.json", 1, 1, "w");
+        let error = Diagnostic::error("a.json", 1, 1, "e");
+        assert!(report(&[], true).is_ok());
+        assert!(report(std::slice::from_ref(&warning), false).is_ok());
+        assert_eq!(report(std::slice::from_ref(&warning), true).unwrap_err().to_string(),
+            "0 error(s) and 1 warning(s) in strict mode");
+        assert_eq!(report(&[warning, error], false).unwrap_err().to_string(), "1 error(s) and 1 warning(s)");
+    }
+}
@@ -0,0 +1,278 @@
+//! Doxygen / Javadoc-style comment parsing.
+//!
+//! Understands `@tag` and `\tag` spellings of `brief`, `param`, `return`,
+//! `complexity`, `note`, `see`, `example` and `deprecated`. Untagged text is
+//! treated as the description; the 42 header banner is ignored.
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParamDoc {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DocComment {
+    pub brief: Option<String>,
+    // untagged paragraphs following the brief
+    pub details: Vec<String>,
+    pub params: Vec<ParamDoc>,
+    pub returns: Option<String>,
+    pub complexity: Option<String>,
+    pub notes: Vec<String>,
+    pub see_also: Vec<String>,
+    pub examples: Vec<String>,
+    pub deprecated: Option<String>,
+}
+
+impl DocComment {
+    /// Brief followed by the detailed paragraphs, on one line.
+    pub fn description(&self) -> Option<String> {
+        let mut parts: Vec<&str> = Vec::new();
+        if let Some(b) = &self.brief {
+            parts.push(b);
+        }
+        parts.extend(self.details.iter().map(String::as_str));
+        if parts.is_empty() { None } else { Some(parts.join(" ")) }
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|p| p.name == name).map(|p| p.description.as_str())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Description,
+    Brief,
+    Param,
+    Return,
+    Complexity,
+    Note,
+    See,
+    Example,
+    Deprecated,
+}
+
+/// The 42 School header: a box of `*` with the `:::      ::::::::` logo.
+pub fn is_banner(comment: &str) -> bool {
+    comment.contains(":::      ::::::::") || comment.contains("+#+") || comment.contains("By: ")
+}
+
+/// Remove comment delimiters and the decorative leading `*` of each line,
+/// keeping relative indentation so `@example` code survives.
+fn strip_delimiters(comment: &str) -> Vec<String> {
+    let body = comment.trim();
+    let lines: Vec<&str> = if let Some(inner) = body.strip_prefix("/*") {
+        let inner = inner.strip_suffix("*/").unwrap_or(inner);
+        let inner = inner.strip_prefix(['*', '!']).unwrap_or(inner);
+        inner.lines().collect()
+    } else {
+        body.lines().collect()
+    };
+    lines
+        .iter()
+        .map(|l| {
+            let t = l.trim_start();
+            let t = if let Some(rest) = t.strip_prefix("//") {
+                rest.strip_prefix(['/', '!']).unwrap_or(rest)
+            } else if t.starts_with('*') && !t.starts_with("*/") {
+                t.trim_start_matches('*')
+            } else {
+                t
+            };
+            // drop the single space conventionally written after the marker
+            t.strip_prefix(' ').unwrap_or(t).trim_end().to_string()
+        })
+        .collect()
+}
+
+fn parse_tag(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('@').or_else(|| line.strip_prefix('\\'))?;
+    let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+    let (tag, tail) = rest.split_at(end);
+    // `@param[in]` direction markers carry no documentation
+    let tail = match tail.strip_prefix('[') {
+        Some(t) => t.split_once(']').map(|(_, r)| r).unwrap_or(t),
+        None => tail,
+    };
+    Some((tag, tail.trim()))
+}
+
+pub fn parse(comment: &str) -> DocComment {
+    let mut doc = DocComment::default();
+    if is_banner(comment) {
+        return doc;
+    }
+
+    let mut section = Section::Description;
+    let mut buf: Vec<String> = Vec::new();
+    let mut param_name = String::new();
+
+    for line in strip_delimiters(comment) {
+        let tag = parse_tag(line.trim_start());
+        let next = tag.and_then(|(tag, _)| match tag {
+            "brief" | "short" => Some(Section::Brief),
+            "param" | "arg" => Some(Section::Param),
+            "return" | "returns" | "retval" => Some(Section::Return),
+            "complexity" => Some(Section::Complexity),
+            "note" | "remark" | "warning" => Some(Section::Note),
+            "see" | "sa" => Some(Section::See),
+            "example" | "code" => Some(Section::Example),
+            "deprecated" => Some(Section::Deprecated),
+            _ => None,
+        });
+        match (next, tag) {
+            (Some(sec), Some((_, tail))) => {
+                flush(&mut doc, section, &param_name, &mut buf);
+                section = sec;
+                let tail = if sec == Section::Param {
+                    let (name, desc) = tail.split_once(char::is_whitespace).unwrap_or((tail, ""));
+                    param_name = name.to_string();
+                    desc.trim()
+                } else {
+                    tail
+                };
+                if !tail.is_empty() {
+                    buf.push(tail.to_string());
+                }
+            }
+            // `@endcode` and unknown tags close an example block
+            (None, Some(_)) if section == Section::Example => {
+                flush(&mut doc, section, &param_name, &mut buf);
+                section = Section::Description;
+            }
+            _ => {
+                // a blank line ends the brief, further text is detail
+                if line.trim().is_empty() && section == Section::Brief {
+                    flush(&mut doc, section, &param_name, &mut buf);
+                    section = Section::Description;
+                    continue;
+                }
+                buf.push(line);
+            }
+        }
+    }
+    flush(&mut doc, section, &param_name, &mut buf);
+    doc
+}
+
+fn flush(doc: &mut DocComment, section: Section, param: &str, buf: &mut Vec<String>) {
+    let lines = std::mem::take(buf);
+    if section == Section::Example {
+        let code = dedent(&lines);
+        if !code.trim().is_empty() {
+            doc.examples.push(code);
+        }
+        return;
+    }
+
+    let text = strip_inline_commands(
+        &lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" "),
+    );
+    match section {
+        Section::Description => {
+            // untagged text: first paragraph is the brief unless @brief was given
+            for para in paragraphs(&lines) {
+                let para = strip_inline_commands(&para);
+                if doc.brief.is_none() {
+                    doc.brief = Some(para);
+                } else {
+                    doc.details.push(para);
+                }
+            }
+        }
+        Section::Brief if !text.is_empty() => doc.brief = Some(text),
+        Section::Param if !param.is_empty() => doc.params.push(ParamDoc {
+            name: param.to_string(),
+            description: text,
+        }),
+        Section::Return if !text.is_empty() => doc.returns = Some(text),
+        Section::Complexity if !text.is_empty() => doc.complexity = Some(text),
+        Section::Note if !text.is_empty() => doc.notes.push(text),
+        Section::See => doc.see_also.extend(
+            text.split(|c: char| c == ',' || c.is_whitespace())
+                .map(|s| s.trim_end_matches("()").to_string())
+                .filter(|s| !s.is_empty()),
+        ),
+        Section::Deprecated => doc.deprecated = Some(text),
+        _ => {}
+    }
+}
+
+/// `@p s`, `@a s`, `@c s` and `@b s` only style the following word.
+fn strip_inline_commands(text: &str) -> String {
+    text.split(' ')
+        .filter(|w| !matches!(*w, "@p" | "@a" | "@c" | "@b" | "@e" | "\\p" | "\\a" | "\\c" | "\\b" | "\\e"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn paragraphs(lines: &[String]) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur: Vec<&str> = Vec::new();
+    for l in lines {
+        let t = l.trim();
+        if t.is_empty() {
+            if !cur.is_empty() {
+                out.push(cur.join(" "));
+                cur.clear();
+            }
+        } else {
+            cur.push(t);
+        }
+    }
+    if !cur.is_empty() {
+        out.push(cur.join(" "));
+    }
+    out
+}
+
+/// Strips the indentation the non-blank lines share and joins them.
+pub(crate) fn dedent<S: AsRef<str>>(lines: &[S]) -> String {
+    let indent = lines
+        .iter()
+        .map(|l| l.as_ref())
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let code: Vec<&str> = lines.iter().map(|l| l.as_ref()).map(|l| l.get(indent..).unwrap_or(l.trim())).collect();
+    code.join("\n").trim_matches('\n').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_fill_their_sections() {
+        let doc = parse("/**\n * @brief Copies a string.\n *\n * Allocates with malloc.\n * @param[in] s the string\n *        to copy\n * @return the copy, or NULL\n * @see ft_strlen, ft_memcpy\n * @note Free it.\n * @deprecated Use ft_strndup.\n */");
+        assert_eq!(doc.description().as_deref(), Some("Copies a string. Allocates with malloc."));
+        assert_eq!(doc.param("s"), Some("the string to copy"));
+        assert_eq!(doc.returns.as_deref(), Some("the copy, or NULL"));
+        assert_eq!(doc.see_also, ["ft_strlen", "ft_memcpy"]);
+        assert_eq!(doc.notes, ["Free it."]);
+        assert_eq!(doc.deprecated.as_deref(), Some("Use ft_strndup."));
+    }
+
+    #[test]
+    fn backslash_tags_and_line_comments_are_understood() {
+        let doc = parse("/// \\brief Length of s.\n/// \\param s the string\n/// \\returns its length");
+        assert_eq!(doc.brief.as_deref(), Some("Length of s."));
+        assert_eq!(doc.param("s"), Some("the string"));
+        assert_eq!(doc.returns.as_deref(), Some("its length"));
+    }
+
+    #[test]
+    fn examples_keep_their_indentation() {
+        let doc = parse("/*\n * @example\n *     if (x)\n *         ft_a(x);\n * @endcode\n * Trailing text.\n */");
+        assert_eq!(doc.examples, ["if (x)\n    ft_a(x);"]);
+        assert_eq!(doc.brief.as_deref(), Some("Trailing text."));
+    }
+
+    #[test]
+    fn the_42_banner_is_ignored() {
+        let banner = "/* ************************************************************************** */\n/*                                                        :::      ::::::::   */\n/*   By: someone <someone@student.42.fr>          +#+  +:+       +#+        */";
+        assert_eq!(parse(banner), DocComment::default());
+    }
+}
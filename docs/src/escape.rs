@@ -0,0 +1,338 @@
+//! Escaping for each place user-derived text ends up in a page, and an
+//! allowlist sanitizer for manual HTML.
+
+/// Text content and quoted attribute values.
+pub fn html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// A quoted JavaScript string literal. Every character that means something
+/// to HTML is written as `\uXXXX`, so the literal is safe inside a
+/// `<script>` block and inside an event-handler attribute alike.
+pub fn js_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('\'');
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\'' | '"' | '`' | '<' | '>' | '&' | '=' | '/' | '\u{2028}' | '\u{2029}' => {
+                out.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+/// Percent-encode a `/`-separated path for use in a URL or `#/route/...`.
+pub fn url_path(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => out.push(b as char),
+            b => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+/// Decode the entities markdown and hand-written HTML commonly use.
+pub fn unescape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => {
+                    let code = if let Some(hex) = entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else if let Some(dec) = entity.strip_prefix('#') {
+                        dec.parse().ok()
+                    } else {
+                        None
+                    };
+                    code.and_then(char::from_u32)
+                }
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a", "abbr", "b", "blockquote", "br", "code", "dd", "del", "div", "dl", "dt", "em",
+    "h1", "h2", "h3", "h4", "h5", "h6", "hr", "i", "img", "kbd", "li", "ol", "p", "pre",
+    "s", "span", "strong", "sub", "sup", "table", "tbody", "td", "tfoot", "th", "thead",
+    "tr", "ul",
+];
+const VOID_TAGS: &[&str] = &["br", "hr", "img"];
+// dropped together with everything inside them
+const DROPPED_TAGS: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "template", "noscript", "textarea", "title", "svg", "math",
+];
+const URL_ATTRIBUTES: &[&str] = &["href", "src"];
+
+fn allowed_attribute(tag: &str, attr: &str) -> bool {
+    match attr {
+        "class" | "title" => true,
+        "href" => tag == "a",
+        "src" | "alt" => tag == "img",
+        "align" => matches!(tag, "td" | "th"),
+        "start" => tag == "ol",
+        _ => false,
+    }
+}
+
+// Relative links and http(s)/mailto only: no `javascript:` or `data:`
+fn safe_url(url: &str) -> bool {
+    let url: String = url.chars().filter(|c| !c.is_whitespace() && !c.is_control()).collect();
+    match url.find(':') {
+        Some(colon) if !url[..colon].contains(['/', '?', '#']) => {
+            matches!(url[..colon].to_ascii_lowercase().as_str(), "http" | "https" | "mailto")
+        }
+        _ => true,
+    }
+}
+
+/// Keep a conservative subset of HTML: known formatting tags, a few
+/// attributes, safe URLs. Everything else is dropped or escaped, and
+/// unbalanced tags are closed so the fragment cannot leak into the page.
+pub fn sanitize_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut open: Vec<String> = Vec::new();
+    let mut rest = input;
+
+    while let Some(lt) = rest.find('<') {
+        push_text(&mut out, &rest[..lt]);
+        rest = &rest[lt..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map(|end| &comment[end + 3..]).unwrap_or("");
+            continue;
+        }
+        let Some((tag, after)) = parse_tag(rest) else {
+            out.push_str("&lt;");
+            rest = &rest[1..];
+            continue;
+        };
+        rest = after;
+
+        if DROPPED_TAGS.contains(&tag.name.as_str()) {
+            if !tag.closing && !tag.self_closing {
+                rest = skip_element(rest, &tag.name);
+            }
+            continue;
+        }
+        if !ALLOWED_TAGS.contains(&tag.name.as_str()) {
+            continue;
+        }
+
+        if tag.closing {
+            if let Some(pos) = open.iter().rposition(|t| *t == tag.name) {
+                for name in open.drain(pos..).rev() {
+                    out.push_str(&format!("</{}>", name));
+                }
+            }
+            continue;
+        }
+
+        out.push('<');
+        out.push_str(&tag.name);
+        for (name, value) in &tag.attributes {
+            if !allowed_attribute(&tag.name, name) {
+                continue;
+            }
+            if URL_ATTRIBUTES.contains(&name.as_str()) && !safe_url(value) {
+                continue;
+            }
+            out.push_str(&format!(" {}=\"{}\"", name, html(value)));
+        }
+        out.push('>');
+        if !VOID_TAGS.contains(&tag.name.as_str()) && !tag.self_closing {
+            open.push(tag.name);
+        }
+    }
+    push_text(&mut out, rest);
+    for name in open.iter().rev() {
+        out.push_str(&format!("</{}>", name));
+    }
+    out
+}
+
+// Text between tags: keep existing entities, escape what would start markup
+fn push_text(out: &mut String, text: &str) {
+    out.push_str(&text.replace('>', "&gt;"));
+}
+
+struct Tag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    attributes: Vec<(String, String)>,
+}
+
+// `s` starts with `<`; returns the tag and what follows its `>`
+fn parse_tag(s: &str) -> Option<(Tag, &str)> {
+    let mut rest = &s[1..];
+    let closing = rest.starts_with('/');
+    if closing {
+        rest = &rest[1..];
+    }
+    let name_len = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+    if name_len == 0 || !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let name = rest[..name_len].to_ascii_lowercase();
+    rest = &rest[name_len..];
+
+    let mut attributes = Vec::new();
+    let mut self_closing = false;
+    loop {
+        rest = rest.trim_start();
+        if let Some(r) = rest.strip_prefix("/>") {
+            self_closing = true;
+            rest = r;
+            break;
+        }
+        if let Some(r) = rest.strip_prefix('>') {
+            rest = r;
+            break;
+        }
+        if let Some(r) = rest.strip_prefix('/') {
+            rest = r;
+            continue;
+        }
+        let attr_len = rest.find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/')).unwrap_or(rest.len());
+        if attr_len == 0 {
+            // unterminated tag
+            return None;
+        }
+        let attr = rest[..attr_len].to_ascii_lowercase();
+        rest = rest[attr_len..].trim_start();
+        let mut value = String::new();
+        if let Some(r) = rest.strip_prefix('=') {
+            let r = r.trim_start();
+            let (raw, after) = match r.chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    let end = r[1..].find(q)?;
+                    (&r[1..end + 1], &r[end + 2..])
+                }
+                _ => {
+                    let end = r.find(|c: char| c.is_whitespace() || c == '>').unwrap_or(r.len());
+                    (&r[..end], &r[end..])
+                }
+            };
+            value = unescape_html(raw);
+            rest = after;
+        }
+        attributes.push((attr, value));
+    }
+    Some((Tag { name, closing, self_closing, attributes }, rest))
+}
+
+// Skip past the `</name>` closing a dropped element
+fn skip_element<'a>(s: &'a str, name: &str) -> &'a str {
+    let lower = s.to_ascii_lowercase();
+    let close = format!("</{}", name);
+    match lower.find(&close) {
+        Some(pos) => s[pos..].find('>').map(|end| &s[pos + end + 1..]).unwrap_or(""),
+        None => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_are_dropped_with_their_content() {
+        assert_eq!(sanitize_html("<p>a<script>alert(1)</script>b</p>"), "<p>ab</p>");
+        assert_eq!(sanitize_html("<p>a<SCRIPT type=\"module\">alert(1)</ScRiPt >b</p>"), "<p>ab</p>");
+        assert_eq!(sanitize_html("<p>a<script>alert(1)"), "<p>a</p>");
+        assert_eq!(sanitize_html("<svg><script>alert(1)</script></svg>ok"), "ok");
+    }
+
+    #[test]
+    fn event_handlers_are_dropped() {
+        assert_eq!(sanitize_html("<img src=\"x.png\" onerror=\"alert(1)\" alt=\"x\">"), "<img src=\"x.png\" alt=\"x\">");
+        assert_eq!(sanitize_html("<a href=\"#top\" ONCLICK=alert(1)>up</a>"), "<a href=\"#top\">up</a>");
+        assert_eq!(sanitize_html("<p/onmouseover='alert(1)'>x</p>"), "<p>x</p>");
+    }
+
+    #[test]
+    fn script_urls_are_dropped() {
+        for href in ["javascript:alert(1)", " JaVaScRiPt:alert(1)", "java\tscript:alert(1)", "data:text/html,<b>", "vbscript:x"] {
+            let html = format!("<a href=\"{}\">x</a>", href);
+            assert_eq!(sanitize_html(&html), "<a>x</a>", "{}", href);
+        }
+    }
+
+    #[test]
+    fn entity_encoded_script_urls_are_dropped() {
+        for href in ["&#106;avascript:alert(1)", "&#x6A;avascript&#58;alert(1)", "java&Tab;script:alert(1)"] {
+            let html = format!("<a href=\"{}\">x</a>", href);
+            assert_eq!(sanitize_html(&html), "<a>x</a>", "{}", href);
+        }
+        // entities left undecoded are escaped again, so the browser sees a
+        // relative link rather than a scheme
+        for href in ["&#106avascript:alert(1)", "javascript&colon;alert(1)"] {
+            let out = sanitize_html(&format!("<a href=\"{}\">x</a>", href));
+            assert!(!unescape_html(&out).contains("javascript:"), "{}", out);
+        }
+        // escaped markup stays text
+        assert_eq!(sanitize_html("&lt;script&gt;alert(1)&lt;/script&gt;"), "&lt;script&gt;alert(1)&lt;/script&gt;");
+    }
+
+    #[test]
+    fn allowed_markup_survives() {
+        let html = "<p class=\"note\">Use <code>ft_strlen</code> <a href=\"https://example.org/a?b=1&amp;c=2\" title=\"t\">first</a>,<br>\
+                    then <a href=\"../functions/ft_strdup.html#top\">this</a> or <a href=\"mailto:me@example.org\">mail</a>.</p>\
+                    <ol start=\"2\"><li><strong>a</strong> &amp; <em>b</em></li></ol>\
+                    <table><tr><td align=\"left\">1</td></tr></table><img src=\"img/a.png\" alt=\"a\">";
+        assert_eq!(sanitize_html(html), html);
+    }
+
+    #[test]
+    fn unbalanced_tags_are_closed() {
+        assert_eq!(sanitize_html("<div><p>open"), "<div><p>open</p></div>");
+        assert_eq!(sanitize_html("<ul><li>a</ul>b</li>"), "<ul><li>a</li></ul>b");
+        assert_eq!(sanitize_html("a < b > c"), "a &lt; b &gt; c");
+    }
+}
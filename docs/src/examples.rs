@@ -0,0 +1,248 @@
+//! Examples run like doctests: each one is compiled with `cc` (or `$CC`)
+//! against the library's `libft.a` and headers, then run with a timeout.
+//! Its stdout becomes `Example.output`, or is checked against the output a
+//! manual already declares.
+
+use crate::config::Selection;
+use crate::diagnostics::{self, Diagnostic};
+use crate::parser::categories_root;
+use crate::test_examples;
+use crate::{LibraryMetadata, PLACEHOLDER_EXAMPLE};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+/// What an example run found.
+#[derive(Debug, Default)]
+pub struct ExampleSummary {
+    // examples without a declared output, now filled in
+    pub captured: usize,
+    // examples whose output matched the declared one
+    pub verified: usize,
+    pub failures: Vec<Diagnostic>,
+}
+
+impl ExampleSummary {
+    /// Print the counts and every failure; fail when there is one.
+    pub fn report(&self) -> anyhow::Result<()> {
+        diagnostics::print(&self.failures);
+        println!("🧪 Ran {} examples: {} outputs captured, {} verified, {} failed",
+            self.captured + self.verified + self.failures.len(), self.captured, self.verified, self.failures.len());
+        if !self.failures.is_empty() {
+            anyhow::bail!("{} example(s) failed", self.failures.len());
+        }
+        Ok(())
+    }
+}
+
+/// Compile and run every real example of the parsed functions (placeholders
+/// and examples harvested from the tests are skipped), filling in or
+/// checking their output. Headers and the archive are looked for in the
+/// files `selection` picks.
+pub fn run(metadata: &mut LibraryMetadata, source: &Path, selection: &Selection, timeout: Duration) -> anyhow::Result<ExampleSummary> {
+    let root = categories_root(source);
+    let archive = find_archive(&root, source, selection)
+        .ok_or_else(|| anyhow::anyhow!("no libft.a in {}: build the library before running examples", root.display()))?;
+    let headers = find_headers(source, selection);
+    let compiler = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let work = std::env::temp_dir().join(format!("libft-docs-examples-{}", std::process::id()));
+    fs::create_dir_all(&work)?;
+
+    let mut names: Vec<String> = metadata.functions.keys().cloned().collect();
+    names.sort();
+    let mut summary = ExampleSummary::default();
+    for name in names {
+        let Some(func) = metadata.functions.get_mut(&name) else { continue };
+        let file = source.join(func.source_file.as_deref().unwrap_or_default());
+        let line = func.line.unwrap_or(1);
+        for (i, example) in func.examples.iter_mut().enumerate() {
+            // harvested test code runs with the test suite, not on its own
+            if example.code.contains(PLACEHOLDER_EXAMPLE) || example.source_file.is_some() {
+                continue;
+            }
+            let program = program(&example.code, &headers);
+            let stem = format!("{}_{}", name, i + 1);
+            let stdout = match compile(&compiler, &work, &stem, &program, &archive, &headers)
+                .and_then(|exe| execute(&exe, &work, timeout))
+            {
+                Ok(stdout) => stdout,
+                Err(message) => {
+                    summary.failures.push(Diagnostic::error(&file, line, 1,
+                        format!("example `{}` of `{}` {}", example.title, name, message)));
+                    continue;
+                }
+            };
+            match &example.output {
+                Some(expected) if expected.trim_end() != stdout.trim_end() => {
+                    // the manual declaring the output is what needs fixing
+                    let (file, line) = example.output_at.clone().unwrap_or((file.clone(), line));
+                    summary.failures.push(Diagnostic::error(&file, line, 1, format!(
+                        "example `{}` of `{}` printed {:?}, expected {:?}",
+                        example.title, name, stdout.trim_end(), expected.trim_end())));
+                }
+                Some(_) => summary.verified += 1,
+                None => {
+                    example.output = Some(stdout);
+                    summary.captured += 1;
+                }
+            }
+        }
+    }
+    let _ = fs::remove_dir_all(&work);
+    Ok(summary)
+}
+
+// libft.a next to the sources, else anywhere a few levels down outside
+// the excluded directories
+fn find_archive(root: &Path, source: &Path, selection: &Selection) -> Option<PathBuf> {
+    [root.join("libft.a"), source.join("libft.a")].into_iter()
+        .find(|p| p.is_file())
+        .or_else(|| {
+            WalkDir::new(root).max_depth(3).into_iter()
+                .filter_entry(|e| {
+                    let rel = e.path().strip_prefix(source).unwrap_or(e.path());
+                    !e.file_type().is_dir() || rel.as_os_str().is_empty() || !selection.excludes_dir(rel)
+                })
+                .filter_map(|e| e.ok())
+                .find(|e| e.file_type().is_file() && e.file_name() == "libft.a")
+                .map(|e| e.into_path())
+        })
+}
+
+// The umbrella `libft.h` when there is one, else every selected header
+// outside the test suite
+fn find_headers(source: &Path, selection: &Selection) -> Vec<PathBuf> {
+    let headers: Vec<PathBuf> = selection.files(source).into_iter()
+        .filter(|p| p.extension().is_some_and(|e| e == "h"))
+        .filter(|p| !test_examples::is_test_file(p.strip_prefix(source).unwrap_or(p)))
+        .collect();
+    match headers.iter().find(|p| p.file_name().is_some_and(|n| n == "libft.h")) {
+        Some(umbrella) => vec![umbrella.clone()],
+        None => headers,
+    }
+}
+
+// A snippet without `main` runs as its body
+fn program(code: &str, headers: &[PathBuf]) -> String {
+    let mut src = String::from("#include <stdio.h>\n#include <stdlib.h>\n#include <string.h>\n#include <unistd.h>\n");
+    for header in headers {
+        src.push_str(&format!("#include \"{}\"\n", header.display()));
+    }
+    src.push('\n');
+    if code.contains("main(") {
+        src.push_str(code);
+    } else {
+        src.push_str("int main(void)\n{\n");
+        for line in code.lines() {
+            src.push_str(&format!("\t{}\n", line));
+        }
+        src.push_str("\treturn (0);\n}\n");
+    }
+    src
+}
+
+fn compile(compiler: &str, work: &Path, stem: &str, program: &str, archive: &Path, headers: &[PathBuf]) -> Result<PathBuf, String> {
+    let source = work.join(format!("{}.c", stem));
+    let exe = work.join(stem);
+    fs::write(&source, program).map_err(|err| format!("could not be written: {}", err))?;
+    let mut cmd = Command::new(compiler);
+    cmd.arg("-w");
+    for dir in headers.iter().filter_map(|h| h.parent()) {
+        cmd.arg("-I").arg(dir);
+    }
+    let output = cmd.arg("-o").arg(&exe).arg(&source).arg(archive).arg("-lm")
+        .output()
+        .map_err(|err| format!("could not run `{}`: {}", compiler, err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let first = stderr.lines().find(|l| l.contains("error")).unwrap_or_else(|| stderr.lines().next().unwrap_or(""));
+        let first = first.trim().trim_start_matches(&format!("{}/", work.display()));
+        return Err(format!("does not compile: {}", first));
+    }
+    Ok(exe)
+}
+
+fn execute(exe: &Path, work: &Path, timeout: Duration) -> Result<String, String> {
+    let mut child = Command::new(exe)
+        .current_dir(work)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("could not be started: {}", err))?;
+    // drained on the side so a chatty example cannot block on a full pipe
+    let mut pipe = child.stdout.take().expect("stdout is piped");
+    let reader = thread::spawn(move || {
+        let mut out = Vec::new();
+        let _ = pipe.read_to_end(&mut out);
+        out
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {}s", timeout.as_secs_f64()));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(err) => return Err(format!("could not be waited for: {}", err)),
+        }
+    };
+    let stdout = String::from_utf8_lossy(&reader.join().unwrap_or_default()).into_owned();
+    if !status.success() {
+        return Err(format!("exited with {}", status));
+    }
+    Ok(stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn tree(name: &str, files: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libft-docs-examples-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for file in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn snippets_are_wrapped_in_main() {
+        let src = program("ft_putstr(\"hi\");", &[PathBuf::from("/lib/libft.h")]);
+        assert!(src.contains("#include \"/lib/libft.h\"\n\nint main(void)\n{\n\tft_putstr(\"hi\");\n\treturn (0);\n}\n"));
+        let src = program("int main(void) { return (1); }", &[]);
+        assert!(src.ends_with("#include <unistd.h>\n\nint main(void) { return (1); }"));
+    }
+
+    #[test]
+    fn the_umbrella_header_is_enough() {
+        let selection = Config::default().selection().unwrap();
+        let dir = tree("umbrella", &["include/libft.h", "include/ft_list.h", "tests/helpers.h", "minilibx-linux/mlx.h"]);
+        assert_eq!(find_headers(&dir, &selection), [dir.join("include/libft.h")]);
+        fs::remove_file(dir.join("include/libft.h")).unwrap();
+        assert_eq!(find_headers(&dir, &selection), [dir.join("include/ft_list.h")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn the_archive_is_not_taken_from_excluded_dirs() {
+        let selection = Config::default().selection().unwrap();
+        let dir = tree("archive", &["build/libft.a", "out/libft.a"]);
+        assert_eq!(find_archive(&dir, &dir, &selection), Some(dir.join("out/libft.a")));
+        fs::remove_dir_all(dir.join("out")).unwrap();
+        assert_eq!(find_archive(&dir, &dir, &selection), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,336 @@
+//! Markdown manuals with YAML (`---`) or TOML (`+++`) front matter. The
+//! front matter holds the manual fields; the body becomes `manual_html` and
+//! its conventional sections (Description, Parameters, Return, Examples)
+//! fill in the fields the front matter leaves out.
+
+use crate::schema::Pos;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Toml,
+}
+
+/// A markdown file split at its front matter delimiters.
+pub struct Document<'a> {
+    pub format: Format,
+    pub front_matter: &'a str,
+    // line of the first front matter line, 1-based
+    pub first_line: usize,
+    pub body: &'a str,
+}
+
+/// `None` when the file does not open with a front matter block, as the
+/// pages JSON manuals point at through `manual_path` don't.
+pub fn split(text: &str) -> Option<Document<'_>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let (format, delimiter) = match text.lines().next()?.trim_end() {
+        "---" => (Format::Yaml, "---"),
+        "+++" => (Format::Toml, "+++"),
+        _ => return None,
+    };
+    let start = text.find('\n')? + 1;
+    let mut offset = start;
+    for line in text[start..].split_inclusive('\n') {
+        if line.trim_end() == delimiter {
+            return Some(Document {
+                format,
+                front_matter: &text[start..offset],
+                first_line: 2,
+                body: &text[offset + line.len()..],
+            });
+        }
+        offset += line.len();
+    }
+    None
+}
+
+impl Document<'_> {
+    /// The front matter as a JSON object, or the located parse error.
+    pub fn fields(&self) -> Result<Map<String, Value>, (Pos, String)> {
+        let value = match self.format {
+            Format::Yaml => serde_yaml::from_str::<Value>(self.front_matter).map_err(|err| {
+                let pos = err.location()
+                    .map(|l| Pos { line: l.line(), column: l.column() })
+                    .unwrap_or(Pos { line: 1, column: 1 });
+                (self.shift(pos), format!("invalid YAML front matter: {}", without_position(&err.to_string())))
+            })?,
+            Format::Toml => {
+                let table = self.front_matter.parse::<toml::Table>().map_err(|err| {
+                    let pos = err.span().map(|s| offset_pos(self.front_matter, s.start)).unwrap_or(Pos { line: 1, column: 1 });
+                    (self.shift(pos), format!("invalid TOML front matter: {}", err.message()))
+                })?;
+                toml_to_json(toml::Value::Table(table))
+            }
+        };
+        match value {
+            Value::Object(map) => Ok(map),
+            // an empty YAML block
+            Value::Null => Ok(Map::new()),
+            other => Err((Pos { line: self.first_line, column: 1 },
+                format!("front matter must be a table of fields, found {}", json_type(&other)))),
+        }
+    }
+
+    /// Position of every top-level field, of its key (`/name#key`) and of
+    /// the items of block lists; anything deeper is located at its field.
+    pub fn positions(&self) -> HashMap<String, Pos> {
+        let mut out = HashMap::new();
+        let mut current: Option<(String, usize)> = None;
+        for (i, line) in self.front_matter.lines().enumerate() {
+            let line_no = self.first_line + i;
+            let trimmed = line.trim_start();
+            let indent = line.len() - trimmed.len();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if indent > 0 || trimmed.starts_with("- ") {
+                if let (Some((key, count)), Some(item)) = (current.as_mut(), trimmed.strip_prefix("- ")) {
+                    let column = indent + 3 + (item.len() - item.trim_start().len());
+                    out.insert(format!("/{}/{}", key, count), Pos { line: line_no, column });
+                    *count += 1;
+                }
+                continue;
+            }
+            let separator = match self.format {
+                Format::Yaml => ':',
+                Format::Toml => '=',
+            };
+            let Some(at) = trimmed.find(separator) else {
+                current = None;
+                continue;
+            };
+            let key = trimmed[..at].trim().trim_matches(|c| c == '"' || c == '\'');
+            let pointer = format!("/{}", key.replace('~', "~0").replace('/', "~1"));
+            let rest = &trimmed[at + 1..];
+            let value_column = at + 2 + (rest.len() - rest.trim_start().len());
+            out.insert(format!("{}#key", pointer), Pos { line: line_no, column: 1 });
+            let value_pos = if rest.trim().is_empty() { Pos { line: line_no, column: 1 } } else { Pos { line: line_no, column: value_column } };
+            out.insert(pointer, value_pos);
+            current = Some((key.to_string(), 0));
+        }
+        out
+    }
+
+    /// Description, parameters, return value and examples found under the
+    /// body's conventional headings, as manual fields.
+    pub fn body_fields(&self, name: &str) -> Map<String, Value> {
+        let mut out = Map::new();
+        for (heading, content) in sections(self.body) {
+            match heading.as_str() {
+                "description" => {
+                    if let Some(paragraph) = first_paragraph(&content) {
+                        out.insert("description".into(), Value::String(paragraph));
+                    }
+                }
+                "parameters" | "params" | "arguments" => {
+                    let params: Vec<Value> = content.iter().filter_map(|l| parameter(l)).map(|(name, description)| {
+                        json!({ "name": name, "type_name": "", "description": description })
+                    }).collect();
+                    if !params.is_empty() {
+                        out.insert("parameters".into(), Value::Array(params));
+                    }
+                }
+                "return" | "returns" | "return value" => {
+                    let text = plain_text(&content);
+                    if !text.is_empty() {
+                        out.insert("return_value".into(), Value::String(text));
+                    }
+                }
+                "example" | "examples" => {
+                    let blocks = code_blocks(&content);
+                    let examples: Vec<Value> = blocks.iter().enumerate().map(|(i, code)| {
+                        let title = if blocks.len() == 1 {
+                            format!("Usage of {}", name)
+                        } else {
+                            format!("Usage of {} ({})", name, i + 1)
+                        };
+                        json!({ "title": title, "code": code, "output": null })
+                    }).collect();
+                    if !examples.is_empty() {
+                        out.insert("examples".into(), Value::Array(examples));
+                    }
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    // front matter positions are relative to the block
+    fn shift(&self, pos: Pos) -> Pos {
+        Pos { line: pos.line + self.first_line - 1, column: pos.column }
+    }
+}
+
+// (lowercased heading, lines up to the next heading), skipping `#` lines
+// inside fenced code
+fn sections(body: &str) -> Vec<(String, Vec<&str>)> {
+    let mut out: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut in_code = false;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+        }
+        if !in_code && trimmed.starts_with('#') {
+            let title = trimmed.trim_start_matches('#').trim().trim_end_matches(':').to_lowercase();
+            out.push((title, Vec::new()));
+            continue;
+        }
+        if let Some((_, lines)) = out.last_mut() {
+            lines.push(line);
+        }
+    }
+    out
+}
+
+fn first_paragraph(lines: &[&str]) -> Option<String> {
+    let paragraph: Vec<&str> = lines.iter()
+        .map(|l| l.trim())
+        .skip_while(|l| l.is_empty())
+        .take_while(|l| !l.is_empty())
+        .collect();
+    if paragraph.is_empty() || paragraph[0].starts_with("```") || paragraph[0].starts_with("- ") {
+        return None;
+    }
+    Some(paragraph.join(" "))
+}
+
+fn plain_text(lines: &[&str]) -> String {
+    lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+// "- `s`: the string" (backticks, colon or dash optional)
+fn parameter(line: &str) -> Option<(String, String)> {
+    let item = line.trim_start().strip_prefix("- ").or_else(|| line.trim_start().strip_prefix("* "))?.trim();
+    let (name, rest) = match item.strip_prefix('`') {
+        Some(quoted) => {
+            let end = quoted.find('`')?;
+            (&quoted[..end], &quoted[end + 1..])
+        }
+        None => {
+            let end = item.find([':', ' '])?;
+            (&item[..end], &item[end..])
+        }
+    };
+    let description = rest.trim_start().trim_start_matches([':', '-', '—', '–']).trim();
+    (!name.is_empty()).then(|| (name.to_string(), description.to_string()))
+}
+
+fn code_blocks(lines: &[&str]) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in lines {
+        let fence = line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~");
+        match (&mut current, fence) {
+            (None, true) => current = Some(Vec::new()),
+            (Some(_), true) => {
+                let code = current.take().unwrap_or_default().join("\n");
+                if !code.trim().is_empty() {
+                    out.push(code);
+                }
+            }
+            (Some(block), false) => block.push(line),
+            (None, false) => {}
+        }
+    }
+    out
+}
+
+// TOML dates have no JSON counterpart: keep them as written
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(table.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect()),
+    }
+}
+
+fn offset_pos(text: &str, offset: usize) -> Pos {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+    Pos { line, column }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Array(_) => "a list",
+        Value::String(_) => "a string",
+        _ => "a scalar",
+    }
+}
+
+// serde_yaml puts " at line L column C" mid-message, relative to the block
+fn without_position(message: &str) -> String {
+    let mut out = String::new();
+    let mut rest = message;
+    while let Some(i) = rest.find(" at line ") {
+        out.push_str(&rest[..i]);
+        rest = rest[i + " at line ".len()..]
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim_start_matches(" column ")
+            .trim_start_matches(|c: char| c.is_ascii_digit());
+    }
+    out + rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = "---\nname: ft_strlen\ntags:\n  - string\n  - easy\n---\n# Description\n\nCounts the bytes\nbefore the NUL.\n\n## Parameters\n\n- `s`: the string\n\n## Return\n\nThe length.\n\n## Example\n\n```c\nft_strlen(\"abc\");\n```\n";
+
+    #[test]
+    fn front_matter_is_split_from_the_body() {
+        let doc = split(YAML).unwrap();
+        assert_eq!(doc.format, Format::Yaml);
+        assert_eq!(doc.front_matter, "name: ft_strlen\ntags:\n  - string\n  - easy\n");
+        assert!(doc.body.starts_with("# Description"));
+        assert!(split("# Just markdown\n").is_none());
+        assert!(split("---\nname: unterminated\n").is_none());
+    }
+
+    #[test]
+    fn yaml_and_toml_give_the_same_fields() {
+        let yaml = split(YAML).unwrap().fields().unwrap();
+        let toml = split("+++\nname = \"ft_strlen\"\ntags = [\"string\", \"easy\"]\n+++\n").unwrap();
+        assert_eq!(toml.format, Format::Toml);
+        assert_eq!(Value::Object(yaml), Value::Object(toml.fields().unwrap()));
+    }
+
+    #[test]
+    fn parse_errors_are_located_in_the_file() {
+        let doc = split("+++\nname = \"ft_a\"\ntags = [\n+++\n").unwrap();
+        let (pos, message) = doc.fields().unwrap_err();
+        assert!(pos.line >= 3, "{:?}", pos);
+        assert!(message.starts_with("invalid TOML front matter"), "{}", message);
+        let (pos, message) = split("---\n- a\n- b\n---\n").unwrap().fields().unwrap_err();
+        assert_eq!(pos, Pos { line: 2, column: 1 });
+        assert_eq!(message, "front matter must be a table of fields, found a list");
+    }
+
+    #[test]
+    fn fields_and_list_items_are_located() {
+        let positions = split(YAML).unwrap().positions();
+        assert_eq!(positions["/name"], Pos { line: 2, column: 7 });
+        assert_eq!(positions["/tags#key"], Pos { line: 3, column: 1 });
+        assert_eq!(positions["/tags/1"], Pos { line: 5, column: 5 });
+    }
+
+    #[test]
+    fn body_sections_fill_in_fields() {
+        let fields = split(YAML).unwrap().body_fields("ft_strlen");
+        assert_eq!(fields["description"], "Counts the bytes before the NUL.");
+        assert_eq!(fields["parameters"], json!([{ "name": "s", "type_name": "", "description": "the string" }]));
+        assert_eq!(fields["return_value"], "The length.");
+        assert_eq!(fields["examples"], json!([{ "title": "Usage of ft_strlen", "code": "ft_strlen(\"abc\");", "output": null }]));
+    }
+}
@@ -0,0 +1,270 @@
+//! Headers as entities: which library headers each one includes, which
+//! functions, types and macros it declares, the header every function
+//! should be included from, and the include graph (DOT, JSON and the SVG of
+//! the headers page).
+
+use crate::call_graph::{self, GraphLink};
+use crate::{escape, HeaderMetadata, LibraryMetadata};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// Space between the columns of the include graph
+const COLUMN_GAP: usize = 60;
+// Room under the nodes for the curved edges
+const DETOUR_MARGIN: usize = 30;
+
+/// Sort the headers, resolve their `#include`s to library headers, fill in
+/// `included_by` and give every function without one its declaring header.
+pub fn link(metadata: &mut LibraryMetadata) {
+    metadata.headers.sort_by(|a, b| a.path.cmp(&b.path));
+    let paths: Vec<String> = metadata.headers.iter().map(|h| h.path.clone()).collect();
+    let mut included_by: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for header in &mut metadata.headers {
+        for include in &mut header.includes {
+            include.header = resolve(&include.name, &header.path, &paths);
+            if let Some(target) = &include.header {
+                included_by.entry(target.clone()).or_default().push(header.path.clone());
+            }
+        }
+    }
+    for header in &mut metadata.headers {
+        header.included_by = included_by.remove(&header.path).unwrap_or_default();
+        header.included_by.dedup();
+    }
+
+    // first header by path wins when a prototype is repeated
+    for header in &metadata.headers {
+        for name in &header.functions {
+            if let Some(func) = metadata.functions.get_mut(name) {
+                func.header.get_or_insert_with(|| header.path.clone());
+            }
+        }
+    }
+}
+
+/// The top-level headers (included by none) that pull in `path`, such as
+/// the `libft.h` umbrella, sorted by path.
+pub fn umbrellas<'a>(metadata: &'a LibraryMetadata, path: &'a str) -> Vec<&'a str> {
+    let by_path: HashMap<&str, &HeaderMetadata> = metadata.headers.iter().map(|h| (h.path.as_str(), h)).collect();
+    let mut seen: HashSet<&str> = HashSet::from([path]);
+    let mut queue = vec![path];
+    let mut out = Vec::new();
+    while let Some(current) = queue.pop() {
+        let Some(header) = by_path.get(current) else { continue };
+        if header.included_by.is_empty() && current != path {
+            out.push(current);
+        }
+        for parent in &header.included_by {
+            if seen.insert(parent.as_str()) {
+                queue.push(parent.as_str());
+            }
+        }
+    }
+    out.sort();
+    out
+}
+
+// `"ft_list.h"` next to the including header first, then anywhere a
+// library header has that name; `-I` makes `<libft.h>` resolve the same way
+fn resolve(name: &str, from: &str, paths: &[String]) -> Option<String> {
+    let dir = from.rsplit_once('/').map(|(dir, _)| dir);
+    let sibling = match dir {
+        Some(dir) => format!("{}/{}", dir, name),
+        None => name.to_string(),
+    };
+    if paths.contains(&sibling) {
+        return Some(sibling);
+    }
+    let suffix = format!("/{}", name);
+    paths.iter().find(|p| p.as_str() == name || p.ends_with(&suffix)).cloned()
+}
+
+/// The library headers and their includes, as written to `include-graph.json`.
+#[derive(Debug, Serialize)]
+pub struct IncludeGraph {
+    pub nodes: Vec<HeaderNode>,
+    pub edges: Vec<call_graph::Edge>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeaderNode {
+    pub path: String,
+    pub name: String,
+    pub guard: Option<String>,
+}
+
+/// Nodes sorted by path, edges by includer in include order.
+pub fn graph(metadata: &LibraryMetadata) -> IncludeGraph {
+    let mut headers: Vec<_> = metadata.headers.iter().collect();
+    headers.sort_by(|a, b| a.path.cmp(&b.path));
+    IncludeGraph {
+        nodes: headers.iter()
+            .map(|h| HeaderNode { path: h.path.clone(), name: h.name.clone(), guard: h.guard.clone() })
+            .collect(),
+        edges: headers.iter()
+            .flat_map(|h| h.includes.iter()
+                .filter_map(|i| i.header.as_ref())
+                .map(|to| call_graph::Edge { from: h.path.clone(), to: to.clone() }))
+            .collect(),
+    }
+}
+
+impl IncludeGraph {
+    /// Graphviz source, headers without a guard drawn dashed.
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut out = String::from("digraph includes {\n    rankdir=LR;\n    node [shape=box, fontname=\"monospace\"];\n");
+        for node in &self.nodes {
+            let style = if node.guard.is_none() { ", style=dashed" } else { "" };
+            out.push_str(&format!("    {} [label={}{}];\n", quote(&node.path), quote(&node.name), style));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!("    {} -> {};\n", quote(&edge.from), quote(&edge.to)));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// An SVG drawn left to right, each header one column right of the
+    /// deepest header including it; `links` maps paths to their pages and
+    /// `current` is highlighted.
+    pub fn to_svg(&self, id: &str, links: &HashMap<String, String>, current: Option<&str>) -> String {
+        let depth = self.depths();
+        let columns = depth.values().copied().max().map_or(1, |d| d + 1);
+        let mut rows: Vec<Vec<&HeaderNode>> = vec![Vec::new(); columns];
+        for node in &self.nodes {
+            rows[depth[node.path.as_str()]].push(node);
+        }
+        let tallest = rows.iter().map(|r| r.len()).max().unwrap_or(1).max(1);
+        let pitch = call_graph::NODE_HEIGHT + call_graph::ROW_GAP;
+        let column_pitch = call_graph::NODE_WIDTH + COLUMN_GAP;
+        // edges skipping a column (or going back) curve below the nodes
+        let detour = |edge: &call_graph::Edge| depth.get(edge.to.as_str()).copied() != depth.get(edge.from.as_str()).map(|d| d + 1);
+        let bottom = tallest * pitch - call_graph::ROW_GAP + 4;
+        let margin = if self.edges.iter().any(detour) { DETOUR_MARGIN } else { 0 };
+        let (width, height) = (columns * column_pitch - COLUMN_GAP + 8, bottom + 4 + margin);
+
+        let mut at: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (column, nodes) in rows.iter().enumerate() {
+            for (i, node) in nodes.iter().enumerate() {
+                at.insert(&node.path, (4 + column * column_pitch, 4 + (tallest - nodes.len()) * pitch / 2 + i * pitch));
+            }
+        }
+        let marker = format!("{}-arrow", id);
+        let mut out = format!(
+            "<svg class=\"call-graph__svg\" viewBox=\"0 0 {} {}\" role=\"img\" aria-label=\"Include graph\">\
+             <defs><marker id=\"{}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+             <path d=\"M0,0 L10,5 L0,10 z\" class=\"call-graph__arrow\"/></marker></defs>",
+            width, height, escape::html(&marker));
+        let middle = call_graph::NODE_HEIGHT / 2;
+        for edge in &self.edges {
+            let (Some(&(x1, y1)), Some(&(x2, y2))) = (at.get(edge.from.as_str()), at.get(edge.to.as_str())) else { continue };
+            let (x1, y1, y2) = (x1 + call_graph::NODE_WIDTH, y1 + middle, y2 + middle);
+            if detour(edge) {
+                let below = bottom + margin;
+                out.push_str(&format!(
+                    "<path class=\"call-graph__edge\" fill=\"none\" d=\"M{},{} C{},{} {},{} {},{}\" marker-end=\"url(#{})\"/>",
+                    x1, y1, x1 + COLUMN_GAP, below, x2.saturating_sub(COLUMN_GAP), below, x2, y2, escape::html(&marker)));
+            } else {
+                out.push_str(&format!(
+                    "<line class=\"call-graph__edge\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" marker-end=\"url(#{})\"/>",
+                    x1, y1, x2, y2, escape::html(&marker)));
+            }
+        }
+        for node in &self.nodes {
+            let (x, y) = at[node.path.as_str()];
+            let link = GraphLink { name: node.name.clone(), href: links.get(&node.path).cloned() };
+            out.push_str(&call_graph::node(&link, x, y, current == Some(node.path.as_str())));
+        }
+        out.push_str("</svg>");
+        out
+    }
+
+    // longest include chain reaching each header; cycles stop growing
+    // once every header has been visited that many times
+    fn depths(&self) -> HashMap<&str, usize> {
+        let mut depth: HashMap<&str, usize> = self.nodes.iter().map(|n| (n.path.as_str(), 0)).collect();
+        for _ in 0..self.nodes.len() {
+            let mut changed = false;
+            for edge in &self.edges {
+                let (Some(&from), Some(&to)) = (depth.get(edge.from.as_str()), depth.get(edge.to.as_str())) else { continue };
+                if to < from + 1 && from + 1 < self.nodes.len() {
+                    depth.insert(edge.to.as_str(), from + 1);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        depth
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn header(path: &str, includes: &[&str], functions: &[&str]) -> serde_json::Value {
+        let name = path.rsplit('/').next().unwrap();
+        let includes: Vec<_> = includes.iter().map(|i| json!({ "name": i })).collect();
+        json!({ "name": name, "path": path, "category": "include", "includes": includes, "functions": functions })
+    }
+
+    fn library() -> LibraryMetadata {
+        serde_json::from_value(json!({
+            "name": "libft", "version": "1", "description": "", "author": "", "categories": ["str"],
+            "functions": {
+                "ft_lstnew": { "name": "ft_lstnew", "category": "list" },
+                "ft_strlen": { "name": "ft_strlen", "category": "str", "header": "str/ft_str.h" },
+            },
+            "headers": [
+                header("libft.h", &["stdlib.h", "ft_list.h", "str/ft_str.h"], &[]),
+                header("list/ft_list.h", &[], &["ft_lstnew"]),
+                header("include/ft_list.h", &[], &["ft_lstnew"]),
+                header("str/ft_str.h", &["ft_list.h"], &["ft_strlen"]),
+            ],
+        })).unwrap()
+    }
+
+    #[test]
+    fn includes_resolve_to_siblings_first() {
+        let mut metadata = library();
+        link(&mut metadata);
+        let paths: Vec<_> = metadata.headers.iter().map(|h| h.path.as_str()).collect();
+        assert_eq!(paths, ["include/ft_list.h", "libft.h", "list/ft_list.h", "str/ft_str.h"]);
+        let libft = &metadata.headers[1];
+        let resolved: Vec<_> = libft.includes.iter().map(|i| i.header.as_deref()).collect();
+        assert_eq!(resolved, [None, Some("include/ft_list.h"), Some("str/ft_str.h")]);
+        assert_eq!(metadata.headers[0].included_by, ["libft.h", "str/ft_str.h"]);
+    }
+
+    #[test]
+    fn functions_get_the_first_declaring_header() {
+        let mut metadata = library();
+        link(&mut metadata);
+        assert_eq!(metadata.functions["ft_lstnew"].header.as_deref(), Some("include/ft_list.h"));
+        assert_eq!(metadata.functions["ft_strlen"].header.as_deref(), Some("str/ft_str.h"));
+    }
+
+    #[test]
+    fn umbrellas_are_the_top_level_includers() {
+        let mut metadata = library();
+        link(&mut metadata);
+        assert_eq!(umbrellas(&metadata, "include/ft_list.h"), ["libft.h"]);
+        assert!(umbrellas(&metadata, "libft.h").is_empty());
+    }
+
+    #[test]
+    fn deeper_includes_go_further_right() {
+        let mut metadata = library();
+        link(&mut metadata);
+        let graph = graph(&metadata);
+        let depths = graph.depths();
+        assert_eq!(depths["libft.h"], 0);
+        assert_eq!(depths["str/ft_str.h"], 1);
+        assert_eq!(depths["include/ft_list.h"], 2);
+        assert!(graph.to_dot().contains("    \"libft.h\" -> \"str/ft_str.h\";\n"));
+    }
+}
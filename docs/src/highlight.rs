@@ -0,0 +1,145 @@
+//! Build-time syntax highlighting.
+//!
+//! Code is turned into `<span class="hl-...">` markup by syntect; colours
+//! live in `highlight.css`, generated from a dark and a light syntect theme,
+//! so themes can switch palettes without re-rendering the pages.
+
+use crate::escape;
+use std::sync::OnceLock;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+const DARK_THEME: &str = "base16-ocean.dark";
+const LIGHT_THEME: &str = "InspiredGitHub";
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn find_syntax(lang: &str) -> &'static SyntaxReference {
+    let ss = syntax_set();
+    ss.find_syntax_by_token(lang)
+        .or_else(|| ss.find_syntax_by_extension("c"))
+        .unwrap_or_else(|| ss.find_syntax_plain_text())
+}
+
+/// Highlight C source; the result is escaped and safe to embed in HTML.
+pub fn highlight_c(code: &str) -> String {
+    highlight(code, "c")
+}
+
+/// Highlight `code` in the language named by a fence token (`c`, `sh`,
+/// `makefile`...), falling back to C for unknown or empty tokens.
+pub fn highlight(code: &str, lang: &str) -> String {
+    let lang = if lang.trim().is_empty() { "c" } else { lang.trim() };
+    let mut gen = ClassedHTMLGenerator::new_with_class_style(find_syntax(lang), syntax_set(), CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        if gen.parse_html_for_line_which_includes_newline(line).is_err() {
+            return escape::html(code);
+        }
+    }
+    gen.finalize()
+}
+
+/// Highlight the fenced code blocks of rendered markdown:
+/// `<pre><code class="language-x">...</code></pre>`.
+pub fn highlight_code_blocks(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find("<pre><code") {
+        out.push_str(&rest[..start]);
+        let block = &rest[start..];
+        // end of the `<code ...>` tag that follows `<pre>`
+        let open_end = block["<pre>".len()..].find('>').map(|p| p + "<pre>".len() + 1);
+        let (Some(open_end), Some(close)) = (open_end, block.find("</code></pre>")) else {
+            out.push_str(block);
+            return out;
+        };
+        let open = &block[..open_end];
+        let lang = open.split("language-").nth(1)
+            .and_then(|s| s.split(['"', ' ']).next())
+            .unwrap_or("");
+        let code = escape::unescape_html(&block[open_end..close]);
+        out.push_str(&format!("<pre class=\"hl-block\"><code class=\"language-{}\">{}</code></pre>",
+            escape::html(if lang.is_empty() { "c" } else { lang }), highlight(&code, lang)));
+        rest = &block[close + "</code></pre>".len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Token colours: the dark palette by default, the light one under
+/// `[data-theme="light"]`.
+pub fn stylesheet() -> anyhow::Result<String> {
+    let themes = ThemeSet::load_defaults();
+    let dark = css_for_theme_with_class_style(&themes.themes[DARK_THEME], CLASS_STYLE)?;
+    let light = css_for_theme_with_class_style(&themes.themes[LIGHT_THEME], CLASS_STYLE)?;
+    Ok(format!("{}\n{}", token_rules(&dark, ""), token_rules(&light, "[data-theme=\"light\"] ")))
+}
+
+// Keep the token colours only (code boxes keep their own background) and
+// scope every selector under `prefix`
+fn token_rules(css: &str, prefix: &str) -> String {
+    let mut out = String::new();
+    for rule in css.split_inclusive('}') {
+        let Some((selectors, body)) = rule.split_once('{') else { continue };
+        let selectors = strip_comments(selectors);
+        let selectors = selectors.trim();
+        if selectors.is_empty() || selectors == ".hl-code" {
+            continue;
+        }
+        let scoped: Vec<String> = selectors.split(',')
+            .map(|s| format!("{}{}", prefix, s.trim()))
+            .collect();
+        out.push_str(&format!("{} {{{}\n", scoped.join(", "), body.trim_end()));
+    }
+    out
+}
+
+fn strip_comments(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("*/") {
+            Some(end) => rest = &rest[start + end + 2..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_is_escaped_and_classed() {
+        let html = highlight_c("if (a < b)\n\treturn (\"<x>\");\n");
+        assert!(html.contains("<span class=\"hl-"));
+        assert!(html.contains("&lt;"));
+        assert!(!html.contains("\"<x>\""));
+    }
+
+    #[test]
+    fn only_fenced_blocks_are_highlighted() {
+        let html = "<p>a &lt; b</p><pre><code class=\"language-sh\">make &amp;&amp; ./a.out\n</code></pre><pre><code>int x;\n</code></pre>";
+        let out = highlight_code_blocks(html);
+        assert!(out.starts_with("<p>a &lt; b</p><pre class=\"hl-block\"><code class=\"language-sh\">"));
+        assert!(out.contains("&amp;&amp;"));
+        assert!(out.contains("<pre class=\"hl-block\"><code class=\"language-c\">"));
+        assert_eq!(highlight_code_blocks("<pre><code>unterminated"), "<pre><code>unterminated");
+    }
+
+    #[test]
+    fn light_rules_are_scoped_and_comments_dropped() {
+        let css = "/* theme */ .hl-code { background: #000; }\n.hl-comment, .hl-string { color: #111; }";
+        assert_eq!(token_rules(css, "[data-theme=\"light\"] "),
+            "[data-theme=\"light\"] .hl-comment, [data-theme=\"light\"] .hl-string { color: #111; }\n");
+    }
+}
@@ -1,5 +1,10 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+// Placeholders used when the source carries no documentation
+pub const NO_DESCRIPTION: &str = "No description available.";
+pub const NO_RETURN_VALUE: &str = "Return value description not available.";
+pub const PLACEHOLDER_EXAMPLE: &str = "// TODO: Add real example";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LibraryMetadata {
@@ -8,9 +13,29 @@ pub struct LibraryMetadata {
     pub description: String,
     pub author: String,
     pub categories: Vec<String>,
+    #[serde(serialize_with = "sorted")]
     pub functions: HashMap<String, FunctionMetadata>,
     #[serde(default)]
     pub order: Vec<String>, // discovery order
+    // static helpers, kept out of `functions` so names may repeat across files
+    #[serde(default)]
+    pub internal_functions: Vec<FunctionMetadata>,
+    // structs, unions, enums and typedefs declared in headers
+    #[serde(default, serialize_with = "sorted")]
+    pub types: HashMap<String, TypeMetadata>,
+    #[serde(default, serialize_with = "sorted")]
+    pub macros: HashMap<String, MacroMetadata>,
+    // every header, sorted by path; see `headers`
+    #[serde(default)]
+    pub headers: Vec<HeaderMetadata>,
+    // problems found in the manuals, see `diagnostics::report`
+    #[serde(skip)]
+    pub diagnostics: Vec<diagnostics::Diagnostic>,
+}
+
+// Maps by key, so that `metadata.json` only changes with the library
+fn sorted<S: serde::Serializer, V: Serialize>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,6 +63,16 @@ pub struct FunctionMetadata {
     pub notes: Vec<String>,
     #[serde(default)]
     pub see_also: Vec<String>,
+    #[serde(default)]
+    pub deprecated: Option<String>,
+    // where the definition (or header prototype) was found
+    #[serde(default)]
+    pub source_file: Option<String>,
+    #[serde(default)]
+    pub line: Option<usize>,
+    // `static` helper, not part of the public API
+    #[serde(default)]
+    pub internal: bool,
 
     // --- SPA manual fields (optional) ---
     #[serde(default)]
@@ -46,10 +81,21 @@ pub struct FunctionMetadata {
     pub author_role: Option<String>,
     #[serde(default)]
     pub related: Vec<String>,
+    // documented functions this one calls, and those calling it; see `call_graph`
+    #[serde(default)]
+    pub calls: Vec<String>,
+    #[serde(default)]
+    pub called_by: Vec<String>,
+    // path of the header declaring it, the one to `#include`
+    #[serde(default)]
+    pub header: Option<String>,
     #[serde(default)]
     pub manual_path: Option<String>,
     #[serde(default)]
     pub manual_html: Option<String>,
+    // documented by a manual under the manual directories; set by the loader
+    #[serde(skip)]
+    pub has_manual: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -64,6 +110,135 @@ pub struct Example {
     pub title: String,
     pub code: String,
     pub output: Option<String>,
+    // the test file an example was harvested from, see `test_examples`
+    #[serde(default)]
+    pub source_file: Option<String>,
+    #[serde(default)]
+    pub line: Option<usize>,
+    // the manual and line declaring `output`; set by the loader
+    #[serde(skip)]
+    pub output_at: Option<(std::path::PathBuf, usize)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TypeKind {
+    Struct,
+    Union,
+    Enum,
+    Typedef,
+}
+
+impl TypeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TypeKind::Struct => "struct",
+            TypeKind::Union => "union",
+            TypeKind::Enum => "enum",
+            TypeKind::Typedef => "typedef",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeMetadata {
+    // typedef name if any (`t_list`), tag otherwise (`s_vec`)
+    pub name: String,
+    pub kind: TypeKind,
+    pub category: String,
+    #[serde(default)]
+    pub category_path: String,
+    // struct/union/enum tag behind a typedef: `s_list`
+    #[serde(default)]
+    pub tag: Option<String>,
+    // aliased type of a plain typedef: `struct s_vec`, `int (*)(void *, void *)`
+    #[serde(default)]
+    pub underlying: Option<String>,
+    // declaration as C source
+    #[serde(default)]
+    pub definition: String,
+    #[serde(default)]
+    pub description: String,
+    // struct/union members
+    #[serde(default)]
+    pub fields: Vec<Parameter>,
+    #[serde(default)]
+    pub values: Vec<EnumValue>,
+    #[serde(default)]
+    pub source_file: Option<String>,
+    #[serde(default)]
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnumValue {
+    pub name: String,
+    // initializer as written (`1 << 0`), none for implicit values
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MacroMetadata {
+    pub name: String,
+    pub category: String,
+    #[serde(default)]
+    pub category_path: String,
+    // parameter names of a function-like macro, none for object-like ones
+    #[serde(default)]
+    pub parameters: Option<Vec<String>>,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub definition: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub source_file: Option<String>,
+    #[serde(default)]
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeaderMetadata {
+    // file name: `libft.h`
+    pub name: String,
+    // relative to the source directory: `include/libft.h`
+    pub path: String,
+    pub category: String,
+    #[serde(default)]
+    pub category_path: String,
+    // `LIBFT_H` of an `#ifndef`/`#define` pair, `#pragma once` as such
+    #[serde(default)]
+    pub guard: Option<String>,
+    #[serde(default)]
+    pub includes: Vec<Include>,
+    // paths of the headers including this one
+    #[serde(default)]
+    pub included_by: Vec<String>,
+    // names declared, in source order
+    #[serde(default)]
+    pub functions: Vec<String>,
+    #[serde(default)]
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub macros: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Include {
+    // as written between the quotes or brackets: `ft_list.h`, `stdlib.h`
+    pub name: String,
+    // `<...>` rather than `"..."`
+    #[serde(default)]
+    pub system: bool,
+    #[serde(default)]
+    pub line: usize,
+    // path of the library header it resolves to, none for the system's
+    #[serde(default)]
+    pub header: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -79,10 +254,286 @@ pub struct SearchableFunction {
     pub category: String,
     pub tags: Vec<String>,
     pub description: String,
+    // name parts and the words their abbreviations stand for
     pub keywords: Vec<String>,
+    // documentation page, relative to the site root
+    pub href: String,
+    // stemmed term -> summed weight of the fields it appears in
+    pub terms: BTreeMap<String, u32>,
 }
 
+pub mod cache;
+pub mod c_parser;
+pub mod call_graph;
+pub mod config;
+pub mod coverage;
+pub mod diagnostics;
+pub mod doc_comment;
+pub mod escape;
+pub mod examples;
+pub mod front_matter;
+pub mod headers;
+pub mod highlight;
+pub mod manual;
 pub mod parser;
-// Remove the missing modules for now - we'll add them as we create them
-pub mod generator;
+pub mod prototypes;
+pub mod renderer;
+pub mod schema;
+pub mod scaffold;
+pub mod search;
+pub mod tags;
 pub mod templates;
+pub mod test_examples;
+pub mod vendor;
+
+pub use parser::LibftParser;
+
+/// What to document and where the site goes.
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    pub source: String,
+    pub output: String,
+    // also list `static` helper functions
+    pub include_internal: bool,
+    // directory of templates/assets overriding the built-in theme
+    pub theme: Option<String>,
+    // everything in one `index.html` instead of a page per category/function
+    pub single_page: bool,
+    // vendored fonts and icons; false links Google Fonts and cdnjs instead
+    pub offline: bool,
+    // fail on manual warnings (orphans, unresolved names) and prototype mismatches too
+    pub strict: bool,
+    // compile and run the examples against libft.a, see `examples::run`
+    pub run_examples: bool,
+    // seconds each example may run
+    pub example_timeout: u64,
+    // `docs.toml`: identity, files, manual directories and tag rules
+    pub config: config::Config,
+    // reuse what unchanged files parsed to, see `cache`
+    pub incremental: bool,
+    // where the cache is kept, outside the output so it is not deployed
+    pub cache_dir: String,
+    // threads parsing files; none means one per CPU
+    pub jobs: Option<usize>,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            source: ".".to_string(),
+            output: "dist".to_string(),
+            include_internal: false,
+            theme: None,
+            single_page: false,
+            offline: true,
+            strict: false,
+            run_examples: false,
+            example_timeout: 5,
+            config: config::Config::default(),
+            incremental: true,
+            cache_dir: cache::DEFAULT_DIR.to_string(),
+            jobs: None,
+        }
+    }
+}
+
+impl BuildOptions {
+    /// The output options of `docs.toml`, for the command line to override.
+    pub fn from_config(config: config::Config) -> Self {
+        Self {
+            source: config.source.dir.clone().unwrap_or_else(|| ".".to_string()),
+            output: config.output.dir.clone().unwrap_or_else(|| "dist".to_string()),
+            include_internal: config.output.include_internal,
+            theme: config.output.theme.clone(),
+            single_page: config.output.single_page,
+            offline: config.output.offline.unwrap_or(true),
+            strict: config.output.strict,
+            cache_dir: config.output.cache_dir.clone().unwrap_or_else(|| cache::DEFAULT_DIR.to_string()),
+            config,
+            ..Self::default()
+        }
+    }
+}
+
+/// Parse the sources and manuals, then write the site into the output
+/// directory: the pages, `metadata.json` and the other JSON and DOT
+/// exports, the stylesheets, the vendored fonts and icons and any theme
+/// assets. With `incremental`, files unchanged since the previous build are
+/// not parsed again (see `cache`); output files are only written when their
+/// content changes.
+pub fn build(options: &BuildOptions) -> anyhow::Result<LibraryMetadata> {
+    let theme = templates::Theme::load(options.theme.as_deref().map(std::path::Path::new))?;
+    let output = std::path::Path::new(&options.output);
+    let mut parser = LibftParser::new(options.source.clone()).with_config(options.config.clone())?;
+    if options.incremental {
+        parser = parser.with_cache(cache::file(std::path::Path::new(&options.cache_dir), std::path::Path::new(&options.source)));
+    }
+    if let Some(jobs) = options.jobs {
+        parser = parser.with_jobs(jobs);
+    }
+    let mut metadata = parser.parse()?;
+    diagnostics::report(&metadata.diagnostics, options.strict)?;
+    let selection = options.config.selection()?;
+    // `check` fails on these; a build only points them out unless strict
+    let prototypes = prototypes::check(std::path::Path::new(&options.source), &selection);
+    if options.strict {
+        diagnostics::report(&prototypes, true)?;
+    } else {
+        diagnostics::print(&prototypes);
+    }
+    if options.run_examples {
+        let timeout = std::time::Duration::from_secs(options.example_timeout);
+        examples::run(&mut metadata, std::path::Path::new(&options.source), &selection, timeout)?.report()?;
+    }
+
+    println!("📝 Found {} functions in {} categories",
+             metadata.functions.len(),
+             metadata.categories.len());
+
+    std::fs::create_dir_all(output)?;
+    renderer::copy_stylesheet(output)?;
+    write_if_changed(&output.join("highlight.css"), highlight::stylesheet()?.as_bytes())?;
+    if options.offline {
+        vendor::write_assets(output)?;
+    }
+    theme.copy_assets(output)?;
+
+    let metadata_json = serde_json::to_string_pretty(&metadata)?;
+    write_if_changed(&output.join("metadata.json"), metadata_json.as_bytes())?;
+    write_if_changed(&output.join("manual.schema.json"), schema::MANUAL_SCHEMA.as_bytes())?;
+
+    let graph = call_graph::graph(&metadata, options.include_internal);
+    write_if_changed(&output.join("call-graph.json"), serde_json::to_string_pretty(&graph)?.as_bytes())?;
+    write_if_changed(&output.join("call-graph.dot"), graph.to_dot().as_bytes())?;
+    let includes = headers::graph(&metadata);
+    write_if_changed(&output.join("include-graph.json"), serde_json::to_string_pretty(&includes)?.as_bytes())?;
+    write_if_changed(&output.join("include-graph.dot"), includes.to_dot().as_bytes())?;
+
+    let index = search::build_index(&metadata, options.include_internal, options.single_page);
+    write_if_changed(&output.join("search-index.json"), serde_json::to_string(&index)?.as_bytes())?;
+
+    let pages = renderer::render_site(&metadata, options, &theme)?;
+    let mut written = 0;
+    for page in &pages {
+        let path = output.join(&page.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if write_if_changed(&path, page.html.as_bytes())? {
+            written += 1;
+        }
+    }
+    let removed = remove_stale_pages(output, &pages)?;
+    if !options.single_page {
+        println!("📄 Wrote {} pages ({} unchanged, {} removed)", written, pages.len() - written, removed);
+    }
+
+    Ok(metadata)
+}
+
+// Pages the previous build wrote but this one did not: renamed, removed or
+// now unlisted functions, headers and categories, or a switch to a single
+// page. Directories left empty go too.
+fn remove_stale_pages(output: &std::path::Path, pages: &[renderer::RenderedPage]) -> std::io::Result<usize> {
+    let current: std::collections::HashSet<std::path::PathBuf> = pages.iter().map(|p| output.join(&p.path)).collect();
+    let mut removed = 0;
+    for dir in ["functions", "headers", "categories"] {
+        // children before their directory, so emptied ones can be removed
+        for entry in walkdir::WalkDir::new(output.join(dir)).contents_first(true) {
+            let Ok(entry) = entry else { continue };
+            let path = entry.path();
+            if entry.file_type().is_dir() {
+                if std::fs::read_dir(path)?.next().is_none() {
+                    std::fs::remove_dir(path)?;
+                }
+            } else if path.extension().is_some_and(|e| e == "html") && !current.contains(path) {
+                std::fs::remove_file(path)?;
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+// Leave files whose content is already there alone, so that watchers and
+// deploys only see what changed; true when written
+fn write_if_changed(path: &std::path::Path, content: &[u8]) -> std::io::Result<bool> {
+    if std::fs::read(path).is_ok_and(|old| old == content) {
+        return Ok(false);
+    }
+    std::fs::write(path, content)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libft-docs-lib-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn page(path: &str) -> renderer::RenderedPage {
+        renderer::RenderedPage { path: PathBuf::from(path), html: String::new() }
+    }
+
+    #[test]
+    fn stale_pages_and_emptied_dirs_are_removed() {
+        let output = temp_dir("stale");
+        for file in ["index.html", "functions/ft_a.html", "functions/ft_old.html", "categories/str/index.html",
+            "categories/gone/deep/index.html", "headers/notes.txt"] {
+            let path = output.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let pages = [page("index.html"), page("functions/ft_a.html"), page("categories/str/index.html")];
+        assert_eq!(remove_stale_pages(&output, &pages).unwrap(), 2);
+        assert!(output.join("functions/ft_a.html").is_file());
+        assert!(!output.join("functions/ft_old.html").exists());
+        assert!(!output.join("categories/gone").exists());
+        assert!(output.join("headers/notes.txt").is_file());
+        std::fs::remove_dir_all(&output).unwrap();
+    }
+
+    #[test]
+    fn unchanged_files_are_not_rewritten() {
+        let output = temp_dir("unchanged");
+        let path = output.join("metadata.json");
+        assert!(write_if_changed(&path, b"{}").unwrap());
+        let written = std::fs::metadata(&path).unwrap().modified().unwrap();
+        assert!(!write_if_changed(&path, b"{}").unwrap());
+        assert_eq!(std::fs::metadata(&path).unwrap().modified().unwrap(), written);
+        assert!(write_if_changed(&path, b"{\"a\": 1}").unwrap());
+        std::fs::remove_dir_all(&output).unwrap();
+    }
+
+    #[test]
+    fn a_second_build_rewrites_nothing() {
+        let dir = temp_dir("rebuild");
+        std::fs::create_dir_all(dir.join("src/str")).unwrap();
+        std::fs::write(dir.join("src/libft.h"), "int\tft_a(int x);\n").unwrap();
+        std::fs::write(dir.join("src/str/ft_a.c"), "int\tft_a(int x)\n{\n\treturn (x);\n}\n").unwrap();
+        let options = BuildOptions {
+            source: dir.join("src").to_string_lossy().to_string(),
+            output: dir.join("dist").to_string_lossy().to_string(),
+            cache_dir: dir.join("cache").to_string_lossy().to_string(),
+            ..BuildOptions::default()
+        };
+        build(&options).unwrap();
+        let page = dir.join("dist/functions/ft_a.html");
+        let written = std::fs::metadata(&page).unwrap().modified().unwrap();
+        build(&options).unwrap();
+        assert_eq!(std::fs::metadata(&page).unwrap().modified().unwrap(), written);
+        assert_eq!(std::fs::read_dir(dir.join("cache")).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_output_dirs_are_fine() {
+        assert_eq!(remove_stale_pages(Path::new("/nonexistent/libft-docs"), &[]).unwrap(), 0);
+    }
+}
@@ -0,0 +1,294 @@
+//! Hand-written manuals: one JSON file per function, optionally pointing at
+//! a markdown page through `manual_path`, or one markdown file with front
+//! matter (see [`front_matter`]). Files are checked against
+//! `schema/manual.schema.json` and problems reported as diagnostics.
+
+use crate::diagnostics::Diagnostic;
+use crate::escape;
+use crate::front_matter;
+use crate::FunctionMetadata;
+use crate::schema::{self, Pos};
+use markdown::to_html;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// A loaded manual, with the position of each of its values.
+pub struct Manual {
+    pub meta: FunctionMetadata,
+    pub path: PathBuf,
+    // front matter completes the parsed function instead of replacing it
+    pub front_matter: bool,
+    positions: HashMap<String, Pos>,
+}
+
+impl Manual {
+    /// Position of the value at a JSON pointer (`/see_also/1`), else of its
+    /// closest located parent, else the start of the file.
+    pub fn diagnostic(&self, pointer: &str, message: String) -> Diagnostic {
+        let pos = self.position(pointer);
+        Diagnostic::warning(&self.path, pos.line, pos.column, message)
+    }
+
+    fn position(&self, mut pointer: &str) -> Pos {
+        loop {
+            if let Some(pos) = self.positions.get(pointer) {
+                return *pos;
+            }
+            match pointer.rfind('/') {
+                Some(i) if i > 0 => pointer = &pointer[..i],
+                _ => return Pos { line: 1, column: 1 },
+            }
+        }
+    }
+}
+
+/// Load every valid manual below `dirs`. Invalid files are left out with an
+/// error diagnostic, which fails the build; questionable ones load with a
+/// warning.
+pub fn load_manuals(dirs: &[PathBuf], diagnostics: &mut Vec<Diagnostic>) -> HashMap<String, Manual> {
+    let mut out: HashMap<String, Manual> = HashMap::new();
+    // docs/ is scanned recursively and contains docs/man
+    let mut seen = HashSet::new();
+    for base in dirs {
+        if !base.is_dir() { continue; }
+        let mut files: Vec<PathBuf> = WalkDir::new(base).min_depth(1).into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file()
+                && matches!(e.path().extension().and_then(|s| s.to_str()), Some("json" | "md")))
+            .map(|e| e.into_path())
+            .collect();
+        files.sort();
+        for path in files {
+            if !seen.insert(fs::canonicalize(&path).unwrap_or_else(|_| path.clone())) {
+                continue;
+            }
+            let Some(manual) = load_manual(&path, diagnostics) else { continue };
+            if let Some(previous) = out.get(&manual.meta.name) {
+                diagnostics.push(manual.diagnostic("/name", format!(
+                    "duplicate manual for `{}`, replacing {}", manual.meta.name, previous.path.display())));
+            }
+            out.insert(manual.meta.name.clone(), manual);
+        }
+    }
+    out
+}
+
+/// Load a JSON manual, or a markdown one with front matter; `None` (without
+/// a diagnostic) for markdown without front matter.
+pub fn load_manual(path: &Path, diagnostics: &mut Vec<Diagnostic>) -> Option<Manual> {
+    let txt = match fs::read_to_string(path) {
+        Ok(txt) => txt,
+        Err(err) => {
+            diagnostics.push(Diagnostic::error(path, 1, 1, format!("cannot read manual: {}", err)));
+            return None;
+        }
+    };
+    if path.extension().and_then(|s| s.to_str()) == Some("md") {
+        return markdown_manual(path, &txt, diagnostics);
+    }
+    json_manual(path, &txt, diagnostics)
+}
+
+fn json_manual(json_path: &Path, txt: &str, diagnostics: &mut Vec<Diagnostic>) -> Option<Manual> {
+    let value: serde_json::Value = match serde_json::from_str(txt) {
+        Ok(value) => value,
+        Err(err) => {
+            diagnostics.push(Diagnostic::error(json_path, err.line().max(1), err.column().max(1),
+                format!("invalid JSON: {}", strip_position(&err.to_string()))));
+            return None;
+        }
+    };
+
+    let positions = schema::positions(txt);
+    let violations = schema::validate_manual(&value, &positions);
+    let meta = checked_metadata(json_path, value, violations, diagnostics)?;
+    finish(meta, json_path, false, positions, None, diagnostics)
+}
+
+fn markdown_manual(md_path: &Path, txt: &str, diagnostics: &mut Vec<Diagnostic>) -> Option<Manual> {
+    let doc = front_matter::split(txt)?;
+    let mut fields = match doc.fields() {
+        Ok(fields) => fields,
+        Err((pos, message)) => {
+            diagnostics.push(Diagnostic::error(md_path, pos.line, pos.column, message));
+            return None;
+        }
+    };
+    let positions = doc.positions();
+    let violations = schema::validate_front_matter(&serde_json::Value::Object(fields.clone()), &positions);
+
+    // the front matter wins over the body's sections
+    let stem = md_path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+    let name = fields.get("name").and_then(|v| v.as_str()).unwrap_or(&stem).to_string();
+    for (key, value) in doc.body_fields(&name) {
+        fields.entry(key).or_insert(value);
+    }
+    fields.entry("name").or_insert_with(|| name.clone().into());
+    fields.entry("category").or_insert_with(|| "".into());
+
+    let meta = checked_metadata(md_path, serde_json::Value::Object(fields), violations, diagnostics)?;
+    finish(meta, md_path, true, positions, Some(to_html(doc.body)), diagnostics)
+}
+
+// Report schema violations; `None` when one of them is fatal
+fn checked_metadata(
+    path: &Path,
+    value: serde_json::Value,
+    violations: Vec<schema::Violation>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<FunctionMetadata> {
+    let mut valid = true;
+    for v in violations {
+        valid &= v.unknown_property;
+        let diagnostic = if v.unknown_property {
+            Diagnostic::warning(path, v.pos.line, v.pos.column, format!("{}, ignored", v.message))
+        } else {
+            Diagnostic::error(path, v.pos.line, v.pos.column, v.message)
+        };
+        diagnostics.push(diagnostic);
+    }
+    if !valid {
+        return None;
+    }
+    match serde_json::from_value(value) {
+        Ok(meta) => Some(meta),
+        Err(err) => {
+            diagnostics.push(Diagnostic::error(path, 1, 1, err.to_string()));
+            None
+        }
+    }
+}
+
+fn finish(
+    mut meta: FunctionMetadata,
+    path: &Path,
+    front_matter: bool,
+    positions: HashMap<String, Pos>,
+    body_html: Option<String>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<Manual> {
+    // fallback name from filename
+    if meta.name.trim().is_empty() {
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            meta.name = stem.to_string();
+        }
+    }
+    // if only category provided, reuse it as path
+    if meta.category_path.trim().is_empty() && !meta.category.trim().is_empty() {
+        meta.category_path = meta.category.clone();
+    }
+    // derive top-level from category_path if missing
+    if meta.category.trim().is_empty() && !meta.category_path.trim().is_empty() {
+        meta.category = meta.category_path.split('/').next().unwrap_or("misc").to_string();
+    }
+    // load manual markdown if present (manual_path is relative to the manual's directory)
+    let mut missing_markdown = None;
+    if let Some(html) = body_html {
+        meta.manual_html = Some(html);
+    } else if let Some(man_rel) = &meta.manual_path {
+        let man_file = path.parent().unwrap_or(Path::new(".")).join(man_rel);
+        match fs::read_to_string(&man_file) {
+            Ok(md) => meta.manual_html = Some(to_html(&md)),
+            Err(err) => missing_markdown = Some(format!("cannot read `manual_path` {}: {}", man_file.display(), err)),
+        }
+    }
+    // markdown output or HTML written straight into the JSON: keep safe markup only
+    meta.manual_html = meta.manual_html.as_deref().map(escape::sanitize_html);
+    meta.has_manual = true;
+
+    let mut manual = Manual { meta, path: path.to_path_buf(), front_matter, positions };
+    // a wrong output is fixed here, not in the source
+    for i in 0..manual.meta.examples.len() {
+        if manual.meta.examples[i].output.is_some() {
+            let line = manual.position(&format!("/examples/{}/output", i)).line;
+            manual.meta.examples[i].output_at = Some((manual.path.clone(), line));
+        }
+    }
+    if let Some(message) = missing_markdown {
+        diagnostics.push(manual.diagnostic("/manual_path", message));
+    }
+    Some(manual)
+}
+
+// serde_json appends " at line L column C"; the diagnostic carries it already
+fn strip_position(message: &str) -> &str {
+    message.rfind(" at line ").map(|i| &message[..i]).unwrap_or(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::Severity;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libft-docs-manual-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn located(diagnostics: &[Diagnostic]) -> Vec<(Severity, usize, usize, &str)> {
+        diagnostics.iter().map(|d| (d.severity, d.line, d.column, d.message.as_str())).collect()
+    }
+
+    #[test]
+    fn invalid_manuals_are_left_out_with_a_located_error() {
+        let dir = temp_dir("invalid");
+        fs::write(dir.join("ft_a.json"), "{\n  \"name\": \"ft_a\",\n  \"category\": \"str\"\n  \"tags\": []\n}").unwrap();
+        fs::write(dir.join("ft_b.json"), "{\n  \"name\": \"ft_b\",\n  \"category\": 1\n}").unwrap();
+        let mut diagnostics = Vec::new();
+        let manuals = load_manuals(std::slice::from_ref(&dir), &mut diagnostics);
+        assert!(manuals.is_empty());
+        assert_eq!(located(&diagnostics), [
+            (Severity::Error, 4, 3, "invalid JSON: expected `,` or `}`"),
+            (Severity::Error, 3, 15, "`category` must be string, found number"),
+        ]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn questionable_manuals_load_with_a_warning() {
+        let dir = temp_dir("warning");
+        fs::write(dir.join("ft_a.json"), "{\n  \"name\": \"\",\n  \"category\": \"\",\n  \"category_path\": \"str/basic\",\n  \"exmaples\": [],\n  \"manual_path\": \"gone.md\"\n}").unwrap();
+        let mut diagnostics = Vec::new();
+        let manual = load_manual(&dir.join("ft_a.json"), &mut diagnostics).unwrap();
+        assert_eq!(manual.meta.name, "ft_a");
+        assert_eq!(manual.meta.category, "str");
+        assert!(manual.meta.has_manual);
+        let found = located(&diagnostics);
+        assert_eq!(found[0], (Severity::Warning, 5, 3, "unknown property `exmaples` in manual (did you mean `examples`?), ignored"));
+        assert_eq!((found[1].0, found[1].1, found[1].2), (Severity::Warning, 6, 18));
+        assert!(found[1].3.starts_with("cannot read `manual_path`"), "{}", found[1].3);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn markdown_manuals_take_their_name_from_the_file() {
+        let dir = temp_dir("markdown");
+        fs::write(dir.join("ft_a.md"), "---\ncategory: str\n---\n# Description\n\nAdds one.\n").unwrap();
+        fs::write(dir.join("notes.md"), "# Not a manual\n").unwrap();
+        let mut diagnostics = Vec::new();
+        let manuals = load_manuals(std::slice::from_ref(&dir), &mut diagnostics);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(manuals.len(), 1);
+        let manual = &manuals["ft_a"];
+        assert!(manual.front_matter);
+        assert_eq!(manual.meta.description, "Adds one.");
+        assert!(manual.meta.manual_html.as_deref().is_some_and(|h| h.contains("<p>Adds one.</p>")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn expected_outputs_remember_their_line() {
+        let dir = temp_dir("output");
+        let path = dir.join("ft_a.json");
+        fs::write(&path, "{\n  \"name\": \"ft_a\",\n  \"category\": \"str\",\n  \"examples\": [\n    {\"title\": \"t\", \"code\": \"x\"},\n    {\"title\": \"u\", \"code\": \"y\",\n     \"output\": \"2\"}\n  ]\n}").unwrap();
+        let manual = load_manual(&path, &mut Vec::new()).unwrap();
+        assert_eq!(manual.meta.examples[0].output_at, None);
+        assert_eq!(manual.meta.examples[1].output_at, Some((path.clone(), 7)));
+        assert_eq!(manual.diagnostic("/examples/1/missing", String::new()).line, 6);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
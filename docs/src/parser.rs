@@ -1,89 +1,123 @@
 use crate::*;
-use regex::Regex;
-use std::collections::HashMap;
+use crate::c_parser::{self, FunctionDecl, MacroDecl, TypeDecl, TypeDeclKind};
+use crate::config::{Config, Selection};
+use crate::doc_comment::{self, DocComment};
+use crate::diagnostics::Diagnostic;
+use crate::cache::{self, Cache, Lookup};
+use crate::manual;
+use crate::tags::Tagger;
+use std::collections::{HashMap, HashSet};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
 use std::fs;
-use std::path::{Path, Component};
+use std::path::{Component, Path, PathBuf};
 use walkdir::WalkDir;
 
+// Categories live under `libft/` when the source tree has one
+pub(crate) fn categories_root(source_dir: &Path) -> PathBuf {
+    let libft = source_dir.join("libft");
+    if libft.is_dir() { libft } else { source_dir.to_path_buf() }
+}
+
+/// What one source or header file parses to on its own; what `cache` keeps
+/// between builds.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ParsedFile {
+    // definitions (`.c`) or `ft_` prototypes (`.h`)
+    pub functions: Vec<ParsedFunction>,
+    pub types: Vec<TypeMetadata>,
+    pub macros: Vec<MacroMetadata>,
+    pub header: Option<HeaderMetadata>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ParsedFunction {
+    pub meta: FunctionMetadata,
+    // identifiers and keywords of the body, for the tag rules
+    pub words: Vec<String>,
+}
+
+// A file found by the walk, and its result
+struct SourceFile {
+    path: PathBuf,
+    rel: String,
+    hash: String,
+    header: bool,
+    parsed: ParsedFile,
+    // parsed by this build rather than taken from the cache
+    fresh: bool,
+}
+
+// What a file still needs once the cache has been asked
+enum Pending {
+    Cached(Box<ParsedFile>),
+    // its content, to parse
+    Parse(String),
+}
+
 pub struct LibftParser {
     source_dir: String,
+    config: Config,
+    selection: Selection,
+    tagger: Tagger,
+    cache: Option<PathBuf>,
+    // threads parsing files; none means one per CPU
+    jobs: Option<usize>,
 }
 
 impl LibftParser {
     pub fn new(source_dir: String) -> Self {
-        Self { source_dir }
+        let config = Config::default();
+        let selection = config.selection().expect("the default globs are valid");
+        let tagger = Tagger::new(&config.tags).expect("the built-in tag rules are valid");
+        Self { source_dir, config, selection, tagger, cache: None, jobs: None }
     }
 
-    fn categories_root(&self) -> std::path::PathBuf {
-        let src = std::path::Path::new(&self.source_dir);
-        let libft = src.join("libft");
-        if libft.is_dir() { libft } else { src.to_path_buf() }
+    /// Identity, files, manual directories and tag rules from `docs.toml`.
+    pub fn with_config(mut self, config: Config) -> anyhow::Result<Self> {
+        self.selection = config.selection()?;
+        self.tagger = Tagger::new(&config.tags)?;
+        self.config = config;
+        Ok(self)
     }
 
-    pub fn parse(&self) -> anyhow::Result<LibraryMetadata> {
-        let mut functions = HashMap::new();
-        let categories = self.discover_categories()?;
-
-        println!("🔍 Scanning source directory: {}", self.source_dir);
-        let mut file_count = 0;
+    /// Reuse what the files unchanged since the last build parsed to, and
+    /// keep this build's results, in the cache file at `path`.
+    pub fn with_cache(mut self, path: PathBuf) -> Self {
+        self.cache = Some(path);
+        self
+    }
 
-        // Parse each source file recursively
-        for entry in WalkDir::new(&self.source_dir)
-            .follow_links(true)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            if entry.file_type().is_file() {
-                if let Some(ext) = entry.path().extension() {
-                    if ext == "c" && !entry.path().to_string_lossy().contains("main.c") {
-                        file_count += 1;
-                        
-                        // Extract function name from basename
-                        let filename = entry.path().file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("unknown");
-                        
-                        // Skip if already processed
-                        if functions.contains_key(filename) {
-                            continue;
-                        }
+    /// Parse files on `jobs` threads rather than one per CPU; the result is
+    /// the same either way.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
 
-                        if let Ok(func_meta) = self.parse_c_file(entry.path()) {
-                            if let Some(meta) = func_meta {
-                                println!("  📄 Parsed: {} ({}) from {}", meta.name, meta.category, entry.path().display());
-                                functions.insert(meta.name.clone(), meta);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    // What the cached results depend on besides the file itself
+    fn cache_settings(&self) -> String {
+        let roots: Vec<String> = self.roots().iter()
+            .map(|r| r.strip_prefix(&self.source_dir).unwrap_or(r).to_string_lossy().to_string())
+            .collect();
+        roots.join("\n")
+    }
 
-        println!("📊 Processed {} C files, found {} functions", file_count, functions.len());
+    fn roots(&self) -> Vec<PathBuf> {
+        self.config.roots(Path::new(&self.source_dir))
+    }
 
-        Ok(LibraryMetadata {
-            name: "libft".to_string(),
-            version: "1.0.0".to_string(),
-            description: "42 School C Library - Extended standard library functions".to_string(),
-            author: "dlesieur".to_string(),
-            categories,
-            functions,
-            order: Vec::new(),
-        })
+    // Documented: selected by the globs and not part of the test suite
+    fn is_documented(&self, path: &Path) -> bool {
+        let rel = path.strip_prefix(&self.source_dir).unwrap_or(path);
+        self.selection.selects(rel) && !test_examples::is_test_file(rel)
     }
 
     fn discover_categories(&self) -> anyhow::Result<Vec<String>> {
-        use std::ffi::OsStr;
-        let src = self.categories_root();
-
-        // Excluded folders that are not code categories
-        const EXCLUDE: &[&str] = &[
-            "docs", "doc", "minilibx-linux", "target", "dist", "website", "bin",
-            "obj", "build", ".git", ".github", ".idea", ".vscode"
-        ];
-
         let mut cats = Vec::new();
-        if src.is_dir() {
+        for src in self.roots() {
+            if !src.is_dir() { continue; }
             for entry in fs::read_dir(src)? {
                 let entry = match entry { Ok(e) => e, Err(_) => continue };
                 let path = entry.path();
@@ -92,7 +126,7 @@ impl LibftParser {
                     Some(n) => n,
                     None => continue,
                 };
-                if name.starts_with('.') || EXCLUDE.contains(&name) { continue; }
+                if name.starts_with('.') { continue; }
                 if self.dir_has_code(&path) {
                     cats.push(name.to_string());
                 }
@@ -103,27 +137,412 @@ impl LibftParser {
         Ok(cats)
     }
 
-    fn dir_has_code(&self, dir: &std::path::Path) -> bool {
-        for e in walkdir::WalkDir::new(dir)
+    fn dir_has_code(&self, dir: &Path) -> bool {
+        WalkDir::new(dir)
             .min_depth(1)
             .max_depth(64)
             .into_iter()
             .filter_map(|e| e.ok())
-        {
-            if e.file_type().is_file() {
-                if let Some(ext) = e.path().extension() {
-                    if ext == "c" || ext == "h" {
-                        return true;
+            .any(|e| e.file_type().is_file()
+                && e.path().extension().is_some_and(|ext| ext == "c" || ext == "h")
+                && self.is_documented(e.path()))
+    }
+
+    pub fn parse(&self) -> anyhow::Result<LibraryMetadata> {
+        let mut functions: HashMap<String, FunctionMetadata> = HashMap::new();
+        let categories = self.discover_categories()?;
+        let mut order: Vec<String> = Vec::new();
+        let mut internal_functions = Vec::new();
+        let mut types = HashMap::new();
+        let mut macros = HashMap::new();
+        let mut headers = Vec::new();
+        // body words by file and function, for the tag rules
+        let mut bodies: HashMap<(String, String), Vec<String>> = HashMap::new();
+
+        println!("🔍 Scanning source directory: {}", self.source_dir);
+        let settings = self.cache_settings();
+        let mut cache = self.cache.as_deref().map(|p| Cache::load(p, &settings)).unwrap_or_default();
+        let mut changes = cache::Changes::default();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(self.jobs.unwrap_or(0)).build()?;
+
+        // Every source/header file, sorted so the merge below sees them in
+        // the same order whatever the file system and thread count
+        let source = Path::new(&self.source_dir);
+        let paths: Vec<(PathBuf, bool)> = self.selection.files(source).into_iter()
+            // the test suite only provides examples (see `test_examples`)
+            .filter(|path| !test_examples::is_test_file(path.strip_prefix(source).unwrap_or(path)))
+            .map(|path| {
+                let header = path.extension().is_some_and(|e| e == "h");
+                (path, header)
+            })
+            .collect();
+
+        // Read and hash them in parallel, take what the cache still has,
+        // then parse the rest in parallel
+        let contents: Vec<Option<String>> = pool.install(|| {
+            paths.par_iter().map(|(path, _)| fs::read_to_string(path).ok()).collect()
+        });
+        let mut pending = Vec::new();
+        for ((path, header), content) in paths.into_iter().zip(contents) {
+            let Some(content) = content else { continue };
+            let rel = self.relative_source(&path);
+            let hash = cache::hash(&content);
+            let parsed = match cache.take(&rel, &hash) {
+                Lookup::Hit(parsed) => {
+                    changes.unchanged += 1;
+                    Pending::Cached(parsed)
+                }
+                Lookup::Changed => {
+                    changes.changed.push(rel.clone());
+                    Pending::Parse(content)
+                }
+                Lookup::New => {
+                    changes.added.push(rel.clone());
+                    Pending::Parse(content)
+                }
+            };
+            pending.push((path, rel, hash, header, parsed));
+        }
+        let files: Vec<SourceFile> = pool.install(|| {
+            pending.into_par_iter()
+                .map(|(path, rel, hash, header, parsed)| {
+                    let (parsed, fresh) = match parsed {
+                        Pending::Cached(parsed) => (*parsed, false),
+                        Pending::Parse(content) => (self.parse_file(&path, &content, header), true),
+                    };
+                    SourceFile { path, rel, hash, header, parsed, fresh }
+                })
+                .collect()
+        });
+        if let Some(path) = &self.cache {
+            changes.removed = cache.remaining().cloned().collect();
+            cache::save(path, &settings, files.iter().map(|f| (f.rel.as_str(), f.hash.as_str(), &f.parsed)))?;
+            changes.report();
+        }
+
+        let file_count = files.iter().filter(|f| !f.header).count();
+        for file in files {
+            let path = file.path.display();
+            if file.header {
+                // header prototypes ensure nodes exist
+                for ParsedFunction { meta, .. } in file.parsed.functions {
+                    if let Some(existing) = functions.get_mut(&meta.name) {
+                        // definition parsed first: fill in what only the header documents
+                        if !is_from_header(existing) {
+                            merge_header_docs(existing, &meta);
+                        }
+                        continue;
+                    }
+                    if !order.iter().any(|n| n == &meta.name) {
+                        order.push(meta.name.clone());
+                    }
+                    if file.fresh {
+                        println!("  📄 Parsed header function: {} ({})", meta.name, meta.category);
+                    }
+                    functions.insert(meta.name.clone(), meta);
+                }
+                for meta in file.parsed.types {
+                    if types.contains_key(&meta.name) {
+                        continue;
+                    }
+                    if file.fresh {
+                        println!("  🧩 Parsed {} {} ({})", meta.kind.as_str(), meta.name, meta.category);
+                    }
+                    types.insert(meta.name.clone(), meta);
+                }
+                for meta in file.parsed.macros {
+                    macros.entry(meta.name.clone()).or_insert(meta);
+                }
+                headers.extend(file.parsed.header);
+                continue;
+            }
+
+            for ParsedFunction { mut meta, words } in file.parsed.functions {
+                bodies.insert((file.rel.clone(), meta.name.clone()), words);
+                if meta.internal {
+                    if file.fresh {
+                        println!("  🔒 Internal: {} from {}", meta.name, path);
+                    }
+                    internal_functions.push(meta);
+                    continue;
+                }
+                // A header prototype only holds the place until the definition shows up
+                if let Some(existing) = functions.remove(&meta.name) {
+                    if !is_from_header(&existing) {
+                        eprintln!("⚠️  Duplicate definition of {} in {} (keeping {})",
+                            meta.name, path,
+                            existing.source_file.as_deref().unwrap_or("?"));
+                        functions.insert(existing.name.clone(), existing);
+                        continue;
                     }
+                    merge_header_docs(&mut meta, &existing);
+                }
+                if !order.iter().any(|n| n == &meta.name) {
+                    order.push(meta.name.clone());
+                }
+                if file.fresh {
+                    println!("  📄 Parsed: {} ({}) from {}", meta.name, meta.category, path);
+                }
+                functions.insert(meta.name.clone(), meta);
+            }
+        }
+
+        println!("📊 Processed {} C files, found {} functions, {} types and {} macros",
+                 file_count, functions.len(), types.len(), macros.len());
+
+        // Load manuals and merge: what a manual sets overrides C/header
+        // parsing, and manuals of functions the sources lack add them
+        let mut diagnostics = Vec::new();
+        let mut manuals: Vec<manual::Manual> = manual::load_manuals(
+            &self.config.manual_dirs(Path::new(&self.source_dir)), &mut diagnostics).into_values().collect();
+        manuals.sort_by(|a, b| a.meta.name.cmp(&b.meta.name));
+        check_manual_names(&manuals, &functions, &internal_functions, &types, &macros, &mut diagnostics);
+        let mut manual_tagged = HashSet::new();
+        for manual in manuals {
+            let mut meta = manual.meta;
+            let name = meta.name.clone();
+            // a `static` helper's manual documents that helper, in its file
+            // when the manual names one
+            if meta.internal || !functions.contains_key(&name) {
+                let helper = internal_functions.iter_mut()
+                    .find(|f| f.name == name && meta.source_file.as_ref().is_none_or(|file| f.source_file.as_ref() == Some(file)));
+                if let Some(helper) = helper {
+                    overlay_manual(helper, meta);
+                    continue;
+                }
+                if meta.internal {
+                    continue;
+                }
+            }
+            if let Some(existing) = functions.get_mut(&name) {
+                // tagged by its author, not by the rules
+                if !manual.front_matter {
+                    manual_tagged.insert(name);
                 }
+                overlay_manual(existing, meta);
+                continue;
             }
+            if meta.category_path.trim().is_empty() {
+                meta.category_path = meta.category.clone();
+            }
+            if meta.category.trim().is_empty() {
+                meta.category = meta.category_path.split('/').next().filter(|c| !c.is_empty()).unwrap_or("misc").to_string();
+            }
+            if !order.iter().any(|n| n == &name) {
+                order.push(name.clone());
+            }
+            // tagged by its author, not by the rules
+            manual_tagged.insert(name.clone());
+            functions.insert(name, meta);
+        }
+
+        let mut metadata = LibraryMetadata {
+            name: self.config.project.name.clone(),
+            version: self.config.project.version.clone(),
+            description: self.config.project.description.clone(),
+            author: self.config.project.author.clone(),
+            categories,
+            functions,
+            order,
+            internal_functions,
+            types,
+            macros,
+            headers,
+            diagnostics,
+        };
+        test_examples::harvest(&mut metadata, Path::new(&self.source_dir), &self.selection);
+        call_graph::link(&mut metadata);
+        headers::link(&mut metadata);
+        for func in metadata.functions.values_mut()
+            .filter(|f| !manual_tagged.contains(&f.name))
+            .chain(metadata.internal_functions.iter_mut())
+        {
+            let words = bodies.get(&(func.source_file.clone().unwrap_or_default(), func.name.clone()));
+            self.tagger.tag(func, words.map_or(&[], Vec::as_slice));
+        }
+        Ok(metadata)
+    }
+
+    // Everything a file yields on its own, before it is merged with the others
+    fn parse_file(&self, path: &Path, content: &str, header: bool) -> ParsedFile {
+        if !header {
+            return ParsedFile { functions: self.parse_c_file(path, content), ..ParsedFile::default() };
+        }
+        ParsedFile {
+            // prototypes, so that functions defined elsewhere still get a node
+            functions: c_parser::parse_functions(content).iter()
+                .filter(|d| !d.is_definition && d.name.starts_with("ft_"))
+                .map(|d| ParsedFunction { meta: self.function_metadata(&d.name, path, Some(d)), words: Vec::new() })
+                .collect(),
+            types: c_parser::parse_types(content).iter().map(|d| self.type_metadata(path, d)).collect(),
+            macros: c_parser::parse_macros(content).iter().map(|d| self.macro_metadata(path, d)).collect(),
+            header: Some(self.header_metadata(path, content)),
+        }
+    }
+
+    // The header as an entity: guard, includes and what it declares
+    fn header_metadata(&self, path: &Path, content: &str) -> HeaderMetadata {
+        let guard = c_parser::include_guard(content);
+        HeaderMetadata {
+            name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            path: self.relative_source(path),
+            category: self.extract_category_from_path(path),
+            category_path: self.extract_category_path_from_path(path),
+            includes: c_parser::parse_includes(content).into_iter()
+                .map(|i| Include { name: i.path, system: i.system, line: i.line, header: None })
+                .collect(),
+            included_by: Vec::new(),
+            functions: c_parser::parse_functions(content).into_iter()
+                .filter(|d| !d.is_definition && !d.is_static())
+                .map(|d| d.name)
+                .collect(),
+            types: c_parser::parse_types(content).into_iter().map(|d| d.name).collect(),
+            macros: c_parser::parse_macros(content).into_iter()
+                .map(|d| d.name)
+                .filter(|name| guard.as_ref() != Some(name))
+                .collect(),
+            guard,
+        }
+    }
+
+    fn type_metadata(&self, path: &Path, decl: &TypeDecl) -> TypeMetadata {
+        let describe = |doc: &Option<String>| doc.as_deref()
+            .and_then(|d| doc_comment::parse(d).description())
+            .unwrap_or_default();
+        TypeMetadata {
+            name: decl.name.clone(),
+            kind: match decl.kind {
+                TypeDeclKind::Struct => TypeKind::Struct,
+                TypeDeclKind::Union => TypeKind::Union,
+                TypeDeclKind::Enum => TypeKind::Enum,
+                TypeDeclKind::Typedef => TypeKind::Typedef,
+            },
+            category: self.extract_category_from_path(path),
+            category_path: self.extract_category_path_from_path(path),
+            tag: decl.tag.clone(),
+            underlying: decl.underlying.clone(),
+            definition: decl.definition(),
+            description: describe(&decl.doc_comment),
+            fields: decl.fields.iter()
+                .map(|f| Parameter {
+                    name: f.name.clone(),
+                    type_name: f.type_name.clone(),
+                    description: describe(&f.doc_comment),
+                })
+                .collect(),
+            values: decl.values.iter()
+                .map(|v| EnumValue {
+                    name: v.name.clone(),
+                    value: v.value.clone(),
+                    description: describe(&v.doc_comment),
+                })
+                .collect(),
+            source_file: Some(self.relative_source(path)),
+            line: Some(decl.line),
         }
-        false
+    }
+
+    fn macro_metadata(&self, path: &Path, decl: &MacroDecl) -> MacroMetadata {
+        MacroMetadata {
+            name: decl.name.clone(),
+            category: self.extract_category_from_path(path),
+            category_path: self.extract_category_path_from_path(path),
+            parameters: decl.parameters.clone(),
+            body: decl.body.clone(),
+            definition: decl.definition(),
+            description: decl.doc_comment.as_deref()
+                .and_then(|d| doc_comment::parse(d).description())
+                .unwrap_or_default(),
+            source_file: Some(self.relative_source(path)),
+            line: Some(decl.line),
+        }
+    }
+
+    // One entry per function defined in the file, with the words of its
+    // body; `static` ones are flagged internal
+    fn parse_c_file(&self, path: &Path, content: &str) -> Vec<ParsedFunction> {
+        let mut bodies: HashMap<String, c_parser::FunctionBody> = c_parser::function_bodies(content)
+            .into_iter()
+            .map(|body| (body.name.clone(), body))
+            .collect();
+        c_parser::parse_functions(content)
+            .iter()
+            .filter(|d| d.is_definition)
+            .map(|d| {
+                let mut meta = self.function_metadata(&d.name, path, Some(d));
+                let body = bodies.remove(&d.name);
+                // everything called for now; `call_graph::link` keeps documented functions
+                meta.calls = body.as_ref().map(|b| b.span.calls.clone()).unwrap_or_default();
+                ParsedFunction { meta, words: body.map(|b| b.words).unwrap_or_default() }
+            })
+            .collect()
+    }
+
+    // Build metadata from a parsed declaration and the comment attached to it
+    fn function_metadata(
+        &self,
+        name: &str,
+        path: &Path,
+        decl: Option<&FunctionDecl>,
+    ) -> FunctionMetadata {
+        let doc = decl
+            .and_then(|d| d.doc_comment.as_deref())
+            .map(doc_comment::parse)
+            .unwrap_or_default();
+
+        // the rules add theirs once headers are linked, see `parse`
+        let mut tags = Vec::new();
+        if doc.deprecated.is_some() {
+            tags.push("deprecated".to_string());
+        }
+        if decl.is_some_and(|d| d.is_static()) {
+            tags.push("internal".to_string());
+        }
+
+        FunctionMetadata {
+            name: name.to_string(),
+            category: self.extract_category_from_path(path),
+            category_path: self.extract_category_path_from_path(path),
+            tags,
+            prototype: self.extract_function_prototype(decl, name),
+            description: self.extract_description(&doc),
+            parameters: self.extract_parameters(decl, &doc),
+            return_value: doc.returns.clone()
+                .unwrap_or_else(|| NO_RETURN_VALUE.to_string()),
+            examples: self.generate_examples(name, &doc),
+            complexity: doc.complexity.clone(),
+            notes: doc.notes.clone(),
+            see_also: doc.see_also.clone(),
+            deprecated: doc.deprecated.clone(),
+            source_file: Some(self.relative_source(path)),
+            line: decl.map(|d| d.line),
+            internal: decl.is_some_and(|d| d.is_static()),
+            updated_at: None,
+            author_role: None,
+            related: Vec::new(),
+            calls: Vec::new(),
+            called_by: Vec::new(),
+            header: None,
+            manual_path: None,
+            manual_html: None,
+            has_manual: false,
+        }
+    }
+
+    fn relative_source(&self, path: &Path) -> String {
+        path.strip_prefix(&self.source_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    // the path below the first category root holding it
+    fn category_relative<'p>(&self, path: &'p Path) -> Option<&'p Path> {
+        self.roots().iter().find_map(|root| path.strip_prefix(root).ok())
     }
 
     fn extract_category_from_path(&self, path: &Path) -> String {
-        let root = self.categories_root();
-        if let Ok(rel) = path.strip_prefix(&root) {
+        if let Some(rel) = self.category_relative(path) {
             if let Some(first) = rel.components().next() {
                 return first.as_os_str().to_string_lossy().to_string();
             }
@@ -132,8 +551,7 @@ impl LibftParser {
     }
 
     fn extract_category_path_from_path(&self, path: &Path) -> String {
-        let root = self.categories_root();
-        if let Ok(rel) = path.strip_prefix(&root) {
+        if let Some(rel) = self.category_relative(path) {
             if let Some(parent) = rel.parent() {
                 let mut parts = Vec::new();
                 for c in parent.components() {
@@ -147,177 +565,289 @@ impl LibftParser {
                 }
             }
         }
-        // fallback to top-level category
         self.extract_category_from_path(path)
     }
 
-    fn parse_c_file(&self, path: &Path) -> anyhow::Result<Option<FunctionMetadata>> {
-        let content = fs::read_to_string(path)?;
-        
-        // Extract function name from filename (basename without extension)
-        let filename = path.file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("unknown");
-
-        // Determine category from path
-        let category = self.extract_category_from_path(path);
-        let category_path = self.extract_category_path_from_path(path);
-
-        // Parse function prototype
-        let prototype = self.extract_function_prototype(&content, filename)?;
-        
-        // Generate metadata
-        let metadata = FunctionMetadata {
-            name: filename.to_string(),
-            category,
-            category_path,
-            tags: self.generate_tags(filename, &content),
-            prototype,
-            description: self.extract_description(&content),
-            parameters: self.extract_parameters(&content),
-            return_value: self.extract_return_value(&content),
-            examples: self.generate_examples(filename),
-            complexity: self.extract_complexity(&content),
-            notes: self.extract_notes(&content),
-            see_also: self.extract_see_also(filename),
-            updated_at: None,
-            author_role: None,
-            related: Vec::new(),
-            manual_path: None,
-            manual_html: None,
-        };
+    fn extract_function_prototype(&self, decl: Option<&FunctionDecl>, func_name: &str) -> String {
+        match decl {
+            Some(d) => d.to_string(),
+            // Fallback: generate from function name
+            None => format!("/* Function: {} */", func_name),
+        }
+    }
 
-        Ok(Some(metadata))
-    }
-
-    fn extract_function_prototype(&self, content: &str, func_name: &str) -> anyhow::Result<String> {
-        // Try multiple patterns to find function definition
-        let patterns = [
-            format!(r"(?m)^[^/\n]*\b{}\s*\([^{{]*\)\s*{{", regex::escape(func_name)),
-            format!(r"(?m)^[^/\n]*\b{}\s*\([^;]*\);", regex::escape(func_name)),
-            format!(r"(?m){}\s*\([^{{;]*", regex::escape(func_name)),
-        ];
-        
-        for pattern in &patterns {
-            if let Ok(func_regex) = Regex::new(pattern) {
-                if let Some(m) = func_regex.find(content) {
-                    let proto = m.as_str()
-                        .trim_end_matches('{')
-                        .trim_end_matches(';')
-                        .trim();
-                    if !proto.is_empty() && proto.len() > func_name.len() {
-                        return Ok(proto.to_string());
-                    }
-                }
+    // Prototype gives names and types, @param tags give descriptions
+    fn extract_parameters(&self, decl: Option<&FunctionDecl>, doc: &DocComment) -> Vec<Parameter> {
+        let mut params: Vec<Parameter> = decl
+            .map(|d| d.parameters.iter()
+                .map(|p| Parameter {
+                    name: p.name.clone(),
+                    type_name: p.type_name.clone(),
+                    description: doc.param(&p.name).unwrap_or_default().to_string(),
+                })
+                .collect())
+            .unwrap_or_default();
+        for p in &doc.params {
+            if !params.iter().any(|q| q.name == p.name) {
+                params.push(Parameter {
+                    name: p.name.clone(),
+                    type_name: String::new(),
+                    description: p.description.clone(),
+                });
             }
         }
+        params
+    }
 
-        // Fallback: generate from function name
-        Ok(format!("/* Function: {} */", func_name))
+    fn generate_examples(&self, func_name: &str, doc: &DocComment) -> Vec<Example> {
+        if !doc.examples.is_empty() {
+            return doc.examples.iter()
+                .enumerate()
+                .map(|(i, code)| Example {
+                    title: if doc.examples.len() == 1 {
+                        format!("Usage of {}", func_name)
+                    } else {
+                        format!("Usage of {} ({})", func_name, i + 1)
+                    },
+                    code: code.clone(),
+                    output: None,
+                    source_file: None,
+                    line: None,
+                    output_at: None,
+                })
+                .collect();
+        }
+        vec![Example {
+            title: format!("Basic usage of {}", func_name),
+            code: format!("// Example usage of {}\n{}", func_name, PLACEHOLDER_EXAMPLE),
+            output: None,
+            source_file: None,
+            line: None,
+            output_at: None,
+        }]
     }
 
-    fn generate_tags(&self, func_name: &str, content: &str) -> Vec<String> {
-        let mut tags = Vec::new();
+    fn extract_description(&self, doc: &DocComment) -> String {
+        doc.description()
+            .unwrap_or_else(|| NO_DESCRIPTION.to_string())
+    }
+}
+
+fn is_from_header(meta: &FunctionMetadata) -> bool {
+    meta.source_file.as_deref().is_some_and(|f| f.ends_with(".h"))
+}
 
-        // Enhanced tag generation based on function name patterns
-        if func_name.starts_with("ft_str") { tags.push("string".to_string()); }
-        if func_name.starts_with("ft_mem") { tags.push("memory".to_string()); }
-        if func_name.starts_with("ft_is") { tags.push("validation".to_string()); }
-        if func_name.starts_with("ft_to") { tags.push("conversion".to_string()); }
-        if func_name.contains("printf") { tags.push("output".to_string()); }
-        if func_name.contains("scanf") { tags.push("input".to_string()); }
-        if func_name.contains("list") { tags.push("linked_list".to_string()); }
-        if func_name.contains("queue") { tags.push("queue".to_string()); }
-        if func_name.contains("vector") { tags.push("vector".to_string()); }
-        if func_name.contains("matrix") { tags.push("matrix".to_string()); }
-        if func_name.contains("sort") { tags.push("sorting".to_string()); }
-        if func_name.contains("search") { tags.push("searching".to_string()); }
-        if func_name.contains("map") { tags.push("data_structure".to_string()); }
-        if func_name.contains("window") { tags.push("graphics".to_string()); }
-        if func_name.contains("render") { tags.push("rendering".to_string()); }
-        if func_name.contains("pool") || func_name.contains("arena") || func_name.contains("slab") {
-            tags.push("memory_management".to_string());
+// Manuals must document a parsed function, and point `see_also`/`related`
+// at something documented
+fn check_manual_names(
+    manuals: &[manual::Manual],
+    functions: &HashMap<String, FunctionMetadata>,
+    internal_functions: &[FunctionMetadata],
+    types: &HashMap<String, TypeMetadata>,
+    macros: &HashMap<String, MacroMetadata>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut known: HashSet<&str> = functions.keys().map(String::as_str).collect();
+    known.extend(internal_functions.iter().map(|f| f.name.as_str()));
+    for manual in manuals {
+        if !known.contains(manual.meta.name.as_str()) {
+            diagnostics.push(manual.diagnostic("/name",
+                format!("orphan manual: no function `{}` in the sources", manual.meta.name)));
         }
+    }
 
-        // Add tags based on content analysis
-        if content.contains("malloc") { tags.push("allocation".to_string()); }
-        if content.contains("free") { tags.push("cleanup".to_string()); }
-        if content.contains("while") || content.contains("for") { tags.push("iteration".to_string()); }
-        if content.contains("recursive") || content.contains("recursion") { tags.push("recursion".to_string()); }
-        if content.contains("mlx_") { tags.push("minilibx".to_string()); }
-        if content.contains("pthread") { tags.push("threading".to_string()); }
-
-        // Add difficulty tags
-        if tags.contains(&"recursion".to_string()) || tags.contains(&"threading".to_string()) {
-            tags.push("advanced".to_string());
-        } else if tags.contains(&"allocation".to_string()) || tags.contains(&"data_structure".to_string()) {
-            tags.push("intermediate".to_string());
-        } else {
-            tags.push("basic".to_string());
+    known.extend(manuals.iter().map(|m| m.meta.name.as_str()));
+    known.extend(types.keys().map(String::as_str));
+    known.extend(types.values().filter_map(|t| t.tag.as_deref()));
+    known.extend(macros.keys().map(String::as_str));
+    for manual in manuals {
+        for (field, names) in [("see_also", &manual.meta.see_also), ("related", &manual.meta.related)] {
+            for (i, target) in names.iter().enumerate() {
+                if !known.contains(target.trim().trim_end_matches("()")) {
+                    diagnostics.push(manual.diagnostic(&format!("/{}/{}", field, i),
+                        format!("unresolved {} `{}`", field, target)));
+                }
+            }
         }
+    }
+}
 
-        tags
-    }
-
-    fn extract_description(&self, content: &str) -> String {
-        // Enhanced comment extraction with multiple patterns
-        let patterns = [
-            r"/\*\*\s*(.*?)\s*\*/",  // /** comment */
-            r"/\*\s*(.*?)\s*\*/",    // /* comment */
-            r"//\s*(.*)",            // // comment
-        ];
-        
-        for pattern in &patterns {
-            if let Ok(regex) = Regex::new(pattern) {
-                if let Some(captures) = regex.captures(content) {
-                    if let Some(comment) = captures.get(1) {
-                        let desc = comment.as_str()
-                            .lines()
-                            .map(|line| line.trim().trim_start_matches('*').trim())
-                            .filter(|line| !line.is_empty() && 
-                                          !line.contains("****************") &&
-                                          !line.contains(":::      ::::::::"))
-                            .collect::<Vec<_>>()
-                            .join(" ");
-                        
-                        if !desc.is_empty() && desc.len() > 10 {
-                            return desc;
-                        }
-                    }
+// What a manual sets wins; the rest stays as parsed
+fn overlay_manual(func: &mut FunctionMetadata, manual: FunctionMetadata) {
+    for (field, value) in [
+        (&mut func.category, manual.category),
+        (&mut func.category_path, manual.category_path),
+        (&mut func.prototype, manual.prototype),
+        (&mut func.description, manual.description),
+        (&mut func.return_value, manual.return_value),
+    ] {
+        if !value.trim().is_empty() {
+            *field = value;
+        }
+    }
+    for tag in manual.tags {
+        if !func.tags.contains(&tag) {
+            func.tags.push(tag);
+        }
+    }
+    for p in manual.parameters {
+        match func.parameters.iter_mut().find(|f| f.name == p.name) {
+            Some(existing) => {
+                if !p.type_name.is_empty() {
+                    existing.type_name = p.type_name;
+                }
+                if !p.description.is_empty() {
+                    existing.description = p.description;
                 }
             }
+            None => func.parameters.push(p),
+        }
+    }
+    if !manual.examples.is_empty() {
+        func.examples = manual.examples;
+    }
+    for (field, value) in [
+        (&mut func.notes, manual.notes),
+        (&mut func.see_also, manual.see_also),
+        (&mut func.related, manual.related),
+    ] {
+        if !value.is_empty() {
+            *field = value;
         }
+    }
+    if manual.deprecated.is_some() && !func.tags.iter().any(|t| t == "deprecated") {
+        func.tags.push("deprecated".to_string());
+    }
+    for (field, value) in [
+        (&mut func.complexity, manual.complexity),
+        (&mut func.deprecated, manual.deprecated),
+        (&mut func.updated_at, manual.updated_at),
+        (&mut func.author_role, manual.author_role),
+        (&mut func.header, manual.header),
+        (&mut func.manual_path, manual.manual_path),
+        (&mut func.manual_html, manual.manual_html),
+    ] {
+        if value.is_some() {
+            *field = value;
+        }
+    }
+    func.has_manual = true;
+}
 
-        "No description available.".to_string()
+// Keep the definition, but take documentation only written above the header prototype
+fn merge_header_docs(def: &mut FunctionMetadata, header: &FunctionMetadata) {
+    if def.description == NO_DESCRIPTION {
+        def.description = header.description.clone();
+    }
+    if def.return_value == NO_RETURN_VALUE {
+        def.return_value = header.return_value.clone();
+    }
+    for p in &mut def.parameters {
+        if p.description.is_empty() {
+            if let Some(h) = header.parameters.iter().find(|h| h.name == p.name) {
+                p.description = h.description.clone();
+            }
+        }
     }
+    if def.complexity.is_none() {
+        def.complexity = header.complexity.clone();
+    }
+    if def.notes.is_empty() {
+        def.notes = header.notes.clone();
+    }
+    if def.see_also.is_empty() {
+        def.see_also = header.see_also.clone();
+    }
+    if def.deprecated.is_none() && header.deprecated.is_some() {
+        def.deprecated = header.deprecated.clone();
+        def.tags.push("deprecated".to_string());
+    }
+}
 
-    fn extract_parameters(&self, _content: &str) -> Vec<Parameter> {
-        // Simplified parameter extraction
-        Vec::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILES: &[(&str, &str)] = &[
+        ("libft.h", "#ifndef LIBFT_H\n# define LIBFT_H\n\n/** @brief Adds one. */\nint\tft_a(int x);\nint\tft_b(int x);\n\n#endif\n"),
+        ("str/ft_a.c", "#include \"../libft.h\"\n\nstatic int\thelper(int x)\n{\n\treturn (x + 1);\n}\n\n/**\n * @param x the number\n */\nint\tft_a(int x)\n{\n\treturn (helper(x));\n}\n"),
+        ("str/ft_b.c", "#include \"../libft.h\"\n\nstatic int\thelper(int x)\n{\n\treturn (x * 2);\n}\n\nint\tft_b(int x)\n{\n\treturn (helper(ft_a(x)));\n}\n"),
+        ("tests/test_a.c", "#include \"../libft.h\"\n\nvoid\ttest_a(void)\n{\n\tft_a(1);\n}\n"),
+        ("docs/man/ft_b.json", "{\"name\": \"ft_b\", \"category\": \"str\", \"description\": \"From the manual.\"}"),
+        ("docs/man/helper.json", "{\"name\": \"helper\", \"category\": \"str\", \"internal\": true, \"source_file\": \"str/ft_b.c\", \"description\": \"Doubles.\"}"),
+    ];
+
+    fn tree(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libft-docs-parser-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (file, content) in FILES {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
     }
 
-    fn extract_return_value(&self, _content: &str) -> String {
-        "Return value description not available.".to_string()
+    fn parse(dir: &Path) -> LibraryMetadata {
+        LibftParser::new(dir.to_string_lossy().to_string()).parse().unwrap()
     }
 
-    fn generate_examples(&self, func_name: &str) -> Vec<Example> {
-        // Generate basic example
-        vec![Example {
-            title: format!("Basic usage of {}", func_name),
-            code: format!("// Example usage of {}\n// TODO: Add real example", func_name),
-            output: None,
-        }]
+    #[test]
+    fn definitions_take_the_header_docs() {
+        let dir = tree("merge");
+        let metadata = parse(&dir);
+        assert_eq!(metadata.categories, ["str"]);
+        let ft_a = &metadata.functions["ft_a"];
+        assert_eq!(ft_a.description, "Adds one.");
+        assert_eq!(ft_a.parameters[0].description, "the number");
+        assert_eq!(ft_a.source_file.as_deref(), Some("str/ft_a.c"));
+        assert_eq!(ft_a.header.as_deref(), Some("libft.h"));
+        assert!(ft_a.examples.iter().any(|e| e.source_file.as_deref() == Some("tests/test_a.c")));
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    fn extract_complexity(&self, _content: &str) -> Option<String> {
-        None
+    #[test]
+    fn static_helpers_stay_internal_per_file() {
+        let dir = tree("internal");
+        let metadata = parse(&dir);
+        assert_eq!(metadata.functions.len(), 2);
+        let helpers: Vec<_> = metadata.internal_functions.iter()
+            .map(|f| (f.source_file.as_deref().unwrap(), f.called_by.clone()))
+            .collect();
+        assert_eq!(helpers, [("str/ft_a.c", vec!["ft_a".to_string()]), ("str/ft_b.c", vec!["ft_b".to_string()])]);
+        assert_eq!(metadata.functions["ft_b"].calls, ["helper", "ft_a"]);
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    fn extract_notes(&self, _content: &str) -> Vec<String> {
-        Vec::new()
+    #[test]
+    fn manuals_overlay_what_they_set() {
+        let dir = tree("manuals");
+        let metadata = parse(&dir);
+        assert!(metadata.diagnostics.is_empty(), "{:?}", metadata.diagnostics);
+        let ft_b = &metadata.functions["ft_b"];
+        assert_eq!(ft_b.description, "From the manual.");
+        assert!(ft_b.has_manual);
+        assert_eq!(ft_b.source_file.as_deref(), Some("str/ft_b.c"));
+        assert_eq!(ft_b.line, Some(8));
+        let descriptions: Vec<_> = metadata.internal_functions.iter().map(|f| f.description.as_str()).collect();
+        assert_ne!(descriptions[0], "Doubles.");
+        assert_eq!(descriptions[1], "Doubles.");
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    fn extract_see_also(&self, _func_name: &str) -> Vec<String> {
-        Vec::new()
+    #[test]
+    fn threads_and_the_cache_do_not_change_the_result() {
+        let dir = tree("jobs");
+        let json = |metadata: &LibraryMetadata| serde_json::to_string(metadata).unwrap();
+        let source = dir.to_string_lossy().to_string();
+        let expected = json(&LibftParser::new(source.clone()).with_jobs(1).parse().unwrap());
+        assert_eq!(json(&LibftParser::new(source.clone()).with_jobs(4).parse().unwrap()), expected);
+
+        let cache = dir.join("target/cache.json");
+        let cached = || LibftParser::new(source.clone()).with_cache(cache.clone()).parse().unwrap();
+        assert_eq!(json(&cached()), expected);
+        assert!(cache.is_file());
+        assert_eq!(json(&cached()), expected);
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -0,0 +1,176 @@
+//! Header prototypes against their definitions: functions declared in a
+//! header but defined nowhere, defined (not `static`) but declared in no
+//! header, and declared with another signature than the definition.
+
+use crate::c_parser::{self, FunctionDecl};
+use crate::config::Selection;
+use crate::diagnostics::{self, Diagnostic, Severity};
+use crate::test_examples;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Defined in every program, declared in none
+const ENTRY_POINT: &str = "main";
+
+// A declaration or definition and where it was found
+struct Found {
+    file: PathBuf,
+    decl: FunctionDecl,
+}
+
+/// Compare every header prototype below `source` with the definitions of the
+/// `.c` files (those `selection` picks), sorted by file and line.
+pub fn check(source: &Path, selection: &Selection) -> Vec<Diagnostic> {
+    let mut declared: BTreeMap<String, Vec<Found>> = BTreeMap::new();
+    let mut defined: BTreeMap<String, Vec<Found>> = BTreeMap::new();
+    let files: Vec<PathBuf> = selection.files(source).into_iter()
+        .filter(|p| !test_examples::is_test_file(p.strip_prefix(source).unwrap_or(p)))
+        .collect();
+
+    for file in files {
+        let Ok(content) = fs::read_to_string(&file) else { continue };
+        let header = file.extension().is_some_and(|x| x == "h");
+        for decl in c_parser::parse_functions(&content) {
+            if decl.is_static() {
+                continue;
+            }
+            // forward declarations in a `.c` file stay local to it
+            let target = match (decl.is_definition, header) {
+                (true, _) => &mut defined,
+                (false, true) => &mut declared,
+                (false, false) => continue,
+            };
+            target.entry(decl.name.clone()).or_default().push(Found { file: file.clone(), decl });
+        }
+    }
+
+    let mut out = Vec::new();
+    for (name, prototypes) in &declared {
+        let Some(definition) = defined.get(name).and_then(|d| d.first()) else {
+            for p in prototypes {
+                out.push(Diagnostic::warning(&p.file, p.decl.line, 1,
+                    format!("`{}` is declared but never defined", name)));
+            }
+            continue;
+        };
+        for p in prototypes {
+            let differences = differences(&p.decl, &definition.decl);
+            if differences.is_empty() {
+                continue;
+            }
+            out.push(Diagnostic::error(&p.file, p.decl.line, 1, format!(
+                "`{}` is declared as `{}` but defined as `{}` at {}:{}: {}",
+                name, p.decl, definition.decl, definition.file.display(), definition.decl.line,
+                differences.join(", "))));
+        }
+    }
+    for (name, definitions) in &defined {
+        if name == ENTRY_POINT || declared.contains_key(name) {
+            continue;
+        }
+        for d in definitions {
+            out.push(Diagnostic::warning(&d.file, d.decl.line, 1,
+                format!("`{}` is defined but declared in no header (make it `static` if it is a helper)", name)));
+        }
+    }
+    out.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    out
+}
+
+/// Print every mismatch and a summary; fail when there is one.
+pub fn report(found: &[Diagnostic]) -> anyhow::Result<()> {
+    diagnostics::print(found);
+    if found.is_empty() {
+        println!("🔗 Header prototypes match their definitions");
+        return Ok(());
+    }
+    let mismatches = found.iter().filter(|d| d.severity == Severity::Error).count();
+    anyhow::bail!("{} signature mismatch(es) and {} missing declaration(s) or definition(s) between headers and sources",
+        mismatches, found.len() - mismatches)
+}
+
+// What differs between the prototype and the definition, parameter names aside
+fn differences(declared: &FunctionDecl, defined: &FunctionDecl) -> Vec<String> {
+    let mut out = Vec::new();
+    if let Some(what) = type_difference(&declared.return_type, &defined.return_type) {
+        out.push(format!("return type {}", what));
+    }
+    if declared.parameters.len() != defined.parameters.len() {
+        out.push(format!("{} parameter(s) declared, {} defined", declared.parameters.len(), defined.parameters.len()));
+    } else {
+        for (i, (a, b)) in declared.parameters.iter().zip(&defined.parameters).enumerate() {
+            if let Some(what) = type_difference(&a.type_name, &b.type_name) {
+                out.push(format!("parameter {} {}", i + 1, what));
+            }
+        }
+    }
+    if declared.is_variadic != defined.is_variadic {
+        out.push(format!("`...` only in the {}", if declared.is_variadic { "declaration" } else { "definition" }));
+    }
+    out
+}
+
+fn type_difference(declared: &str, defined: &str) -> Option<String> {
+    let (a, b) = (normalize(declared), normalize(defined));
+    if a == b {
+        return None;
+    }
+    let without_const = |t: &str| t.split(' ').filter(|w| *w != "const").collect::<Vec<_>>().join(" ");
+    let kind = if without_const(&a) == without_const(&b) { "constness" } else { "type" };
+    Some(format!("{} differs: `{}` vs `{}`", kind, declared.trim(), defined.trim()))
+}
+
+// `char  *` and `char*` alike (`char *`), `char []` as `char *`
+fn normalize(type_name: &str) -> String {
+    let mut t = type_name.trim().to_string();
+    if let Some(open) = t.rfind('[') {
+        if t.ends_with(']') && !t.contains("(*") {
+            t.replace_range(open.., "*");
+        }
+    }
+    t.replace('*', " * ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decl(src: &str) -> FunctionDecl {
+        c_parser::parse_functions(src).remove(0)
+    }
+
+    #[test]
+    fn arrays_and_pointers_normalize_alike() {
+        assert_eq!(normalize("char[]"), "char *");
+        assert_eq!(normalize("char  *"), "char *");
+        assert_eq!(normalize("char**"), "char * *");
+        assert_eq!(type_difference("char []", "char*"), None);
+    }
+
+    #[test]
+    fn constness_is_told_apart_from_type() {
+        let what = type_difference("const char *", "char *").unwrap();
+        assert!(what.starts_with("constness differs"), "{}", what);
+        let what = type_difference("int", "long").unwrap();
+        assert!(what.starts_with("type differs"), "{}", what);
+    }
+
+    #[test]
+    fn parameter_names_do_not_matter() {
+        let declared = decl("size_t\tft_strlen(const char s[]);");
+        let defined = decl("size_t\tft_strlen(const char *str)\n{\n\treturn (0);\n}\n");
+        assert!(differences(&declared, &defined).is_empty());
+    }
+
+    #[test]
+    fn every_difference_is_listed() {
+        let declared = decl("int\tft_printf(char *fmt, int n);");
+        let defined = decl("long\tft_printf(const char *fmt, ...)\n{\n\treturn (0);\n}\n");
+        assert_eq!(differences(&declared, &defined), [
+            "return type type differs: `int` vs `long`",
+            "2 parameter(s) declared, 1 defined",
+            "`...` only in the definition",
+        ]);
+    }
+}
@@ -0,0 +1,985 @@
+use crate::*;
+use crate::config::TagGroup;
+use crate::escape;
+use crate::highlight;
+use crate::templates::Theme;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Anchor/id helpers and grouping by full path
+fn sanitize_id(s: &str) -> String {
+    s.to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+// `linked_list` -> `Linked list`
+fn title_case(s: &str) -> String {
+    let words = s.replace('_', " ");
+    let mut chars = words.chars();
+    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+// Public functions, followed by static helpers when requested
+pub(crate) fn listed_functions(metadata: &LibraryMetadata, include_internal: bool) -> Vec<&FunctionMetadata> {
+    let mut funcs: Vec<&FunctionMetadata> = metadata.functions.values().collect();
+    if include_internal {
+        funcs.extend(metadata.internal_functions.iter());
+    }
+    funcs
+}
+
+fn group_functions_by_path<'a>(
+    functions: &[&'a FunctionMetadata],
+    order: &'a [String],
+) -> BTreeMap<String, Vec<&'a FunctionMetadata>> {
+    let mut grouped: BTreeMap<String, Vec<&FunctionMetadata>> = BTreeMap::new();
+
+    // Build order map for stable ordering
+    let order_map: HashMap<&str, usize> = order.iter().enumerate().map(|(i, n)| (n.as_str(), i)).collect();
+
+    for &f in functions {
+        grouped.entry(category_key(&f.category, &f.category_path)).or_default().push(f);
+    }
+    for v in grouped.values_mut() {
+        v.sort_by_key(|f| {
+            let pos = order_map.get(f.name.as_str()).copied().unwrap_or(usize::MAX);
+            (pos, f.name.as_str())
+        });
+    }
+    grouped
+}
+
+fn sanitize_tag_class(tag: &str) -> String {
+    tag.to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+// Header-declared entities shown next to the functions of their category
+enum TypeCard<'a> {
+    Type(&'a TypeMetadata),
+    Macro(&'a MacroMetadata),
+}
+
+impl TypeCard<'_> {
+    fn name(&self) -> &str {
+        match self {
+            TypeCard::Type(t) => &t.name,
+            TypeCard::Macro(m) => &m.name,
+        }
+    }
+
+    fn kind(&self) -> &str {
+        match self {
+            TypeCard::Type(t) => t.kind.as_str(),
+            TypeCard::Macro(m) if m.parameters.is_some() => "macro()",
+            TypeCard::Macro(_) => "macro",
+        }
+    }
+
+    fn icon(&self) -> &str {
+        match self {
+            TypeCard::Type(t) if t.kind == TypeKind::Enum => "fas fa-list-ol",
+            TypeCard::Type(t) if t.kind == TypeKind::Typedef => "fas fa-link",
+            TypeCard::Type(_) => "fas fa-cube",
+            TypeCard::Macro(_) => "fas fa-hashtag",
+        }
+    }
+}
+
+fn group_types_by_path(metadata: &LibraryMetadata) -> BTreeMap<String, Vec<TypeCard<'_>>> {
+    let mut grouped: BTreeMap<String, Vec<TypeCard>> = BTreeMap::new();
+    for t in metadata.types.values() {
+        grouped.entry(category_key(&t.category, &t.category_path)).or_default().push(TypeCard::Type(t));
+    }
+    for m in metadata.macros.values() {
+        grouped.entry(category_key(&m.category, &m.category_path)).or_default().push(TypeCard::Macro(m));
+    }
+    // types first, then macros, each alphabetically
+    for v in grouped.values_mut() {
+        v.sort_by(|a, b| {
+            matches!(a, TypeCard::Macro(_)).cmp(&matches!(b, TypeCard::Macro(_)))
+                .then_with(|| a.name().cmp(b.name()))
+        });
+    }
+    grouped
+}
+
+// Where each page lives: hash routes of the single page, or files of the
+// multi-page site (written relative to the site root, see `<base>`)
+#[derive(Clone, Copy)]
+struct Links {
+    single_page: bool,
+}
+
+impl Links {
+    fn home(&self) -> String {
+        if self.single_page { "#/".to_string() } else { "index.html".to_string() }
+    }
+
+    fn category(&self, path: &str) -> String {
+        if self.single_page {
+            format!("#/category/{}", escape::url_path(path))
+        } else {
+            format!("{}/index.html", category_dir(path))
+        }
+    }
+
+    // `file` tells apart static helpers of the same name
+    fn function(&self, name: &str, file: Option<&str>) -> String {
+        match file {
+            _ if self.single_page => format!("#/function/{}", escape::url_path(name)),
+            Some(file) => format!("functions/{}--{}.html", slug(name), slug(file)),
+            None => format!("functions/{}.html", slug(name)),
+        }
+    }
+
+    fn headers(&self) -> String {
+        if self.single_page { "#/headers".to_string() } else { "headers/index.html".to_string() }
+    }
+
+    fn header(&self, path: &str) -> String {
+        if self.single_page {
+            format!("#/header/{}", escape::url_path(path))
+        } else {
+            format!("headers/{}.html", slug(path))
+        }
+    }
+
+    fn type_card(&self, name: &str, path: &str) -> String {
+        if self.single_page {
+            format!("#/type/{}", escape::url_path(name))
+        } else {
+            format!("{}#{}", self.category(path), type_card_id(name))
+        }
+    }
+}
+
+/// The href of each of `functions`, in order. A static helper named like
+/// another listed function has its source file in its page name.
+pub(crate) fn function_hrefs(functions: &[&FunctionMetadata], single_page: bool) -> Vec<String> {
+    let links = Links { single_page };
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for func in functions {
+        *counts.entry(&func.name).or_default() += 1;
+    }
+    functions.iter()
+        .map(|func| {
+            let shared = func.internal && counts[func.name.as_str()] > 1;
+            links.function(&func.name, func.source_file.as_deref().filter(|_| shared))
+        })
+        .collect()
+}
+
+// File-name safe form of a function name or path segment
+fn slug(s: &str) -> String {
+    let slug: String = s.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') { c } else { '_' })
+        .collect();
+    if slug.is_empty() || slug.chars().all(|c| c == '.') { "_".to_string() } else { slug }
+}
+
+fn category_dir(path: &str) -> String {
+    let segments: Vec<String> = path.split('/').map(slug).collect();
+    format!("categories/{}", segments.join("/"))
+}
+
+fn type_card_id(name: &str) -> String {
+    sanitize_id(&format!("type-{}", name))
+}
+
+fn category_key(category: &str, path: &str) -> String {
+    if path.trim().is_empty() { category.to_string() } else { path.to_string() }
+}
+
+// Identifier -> link to the documented type (tags resolve to their typedef)
+fn type_links(metadata: &LibraryMetadata, links: Links) -> HashMap<String, String> {
+    let href = |t: &TypeMetadata| links.type_card(&t.name, &category_key(&t.category, &t.category_path));
+    let mut hrefs = HashMap::new();
+    for t in metadata.types.values() {
+        if let Some(tag) = &t.tag {
+            hrefs.entry(tag.clone()).or_insert_with(|| href(t));
+        }
+    }
+    for t in metadata.types.values() {
+        hrefs.insert(t.name.clone(), href(t));
+    }
+    hrefs
+}
+
+// Highlight C code and turn documented type names into links to their card
+fn link_types(code: &str, links: &HashMap<String, String>) -> String {
+    let html = highlight::highlight_c(code);
+    let mut out = String::with_capacity(html.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        if word.is_empty() {
+            return;
+        }
+        match links.get(word.as_str()) {
+            Some(href) => out.push_str(&format!(
+                "<a class=\"type-link\" href=\"{}\">{}</a>", escape::html(href), word)),
+            None => out.push_str(word),
+        }
+        word.clear();
+    };
+    // only text between tags is code; markup and entities pass through
+    let mut chars = html.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '<' | '&' => {
+                flush(&mut word, &mut out);
+                let end = if c == '<' { '>' } else { ';' };
+                out.push(c);
+                for d in chars.by_ref() {
+                    out.push(d);
+                    if d == end {
+                        break;
+                    }
+                }
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => word.push(c),
+            c => {
+                flush(&mut word, &mut out);
+                out.push(c);
+            }
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+fn type_card_view(card: &TypeCard, links: &HashMap<String, String>) -> TypeCardView {
+    let (description, definition, source, line) = match card {
+        TypeCard::Type(t) => (&t.description, &t.definition, &t.source_file, t.line),
+        TypeCard::Macro(m) => (&m.description, &m.definition, &m.source_file, m.line),
+    };
+    // member and enumerator documentation, when any was written
+    let members = match card {
+        TypeCard::Type(t) => t.fields.iter().map(|f| (&f.name, &f.description))
+            .chain(t.values.iter().map(|v| (&v.name, &v.description)))
+            .filter(|(_, d)| !d.is_empty())
+            .map(|(name, description)| MemberView { name: name.clone(), description: description.clone() })
+            .collect(),
+        TypeCard::Macro(_) => Vec::new(),
+    };
+    TypeCardView {
+        id: type_card_id(card.name()),
+        name: card.name().to_string(),
+        kind: card.kind().to_string(),
+        icon: card.icon().to_string(),
+        description: description.clone(),
+        location: location(source, line),
+        definition_html: link_types(definition, links),
+        members,
+    }
+}
+
+// `file:line` shown under a card's description
+fn location(source: &Option<String>, line: Option<usize>) -> Option<String> {
+    source.as_ref().map(|source| match line {
+        Some(line) => format!("{}:{}", source, line),
+        None => source.clone(),
+    })
+}
+
+// The header as a card: guard, includes both ways and what it declares
+fn header_view(header: &HeaderMetadata, site: &Site<'_>) -> HeaderView {
+    let header_link = |path: &String| NameLink {
+        name: site.header_names.get(path).cloned().unwrap_or_else(|| path.clone()),
+        href: site.header_hrefs.get(path).cloned(),
+    };
+    let linked = |names: &[String], hrefs: &HashMap<String, String>| names.iter()
+        .map(|name| NameLink { name: name.clone(), href: hrefs.get(name).cloned() })
+        .collect();
+    HeaderView {
+        id: sanitize_id(&format!("header-{}", header.path)),
+        name: header.name.clone(),
+        path: header.path.clone(),
+        href: site.links.header(&header.path),
+        guard: header.guard.clone(),
+        includes: header.includes.iter()
+            .map(|i| IncludeView {
+                directive: if i.system { format!("#include <{}>", i.name) } else { format!("#include \"{}\"", i.name) },
+                href: i.header.as_ref().and_then(|path| site.header_hrefs.get(path).cloned()),
+                via: Vec::new(),
+            })
+            .collect(),
+        included_by: header.included_by.iter().map(header_link).collect(),
+        functions: linked(&header.functions, &site.function_hrefs),
+        types: linked(&header.types, &site.type_hrefs),
+        macros: header.macros.clone(),
+    }
+}
+
+fn function_card_view<'a>(func: &'a FunctionMetadata, site: &Site<'_>) -> FunctionCardView<'a> {
+    let type_hrefs = &site.type_hrefs;
+    let graph_link = |name: &String| call_graph::GraphLink { name: name.clone(), href: site.function_link(name, func) };
+    let callers: Vec<call_graph::GraphLink> = func.called_by.iter().map(graph_link).collect();
+    let callees: Vec<call_graph::GraphLink> = func.calls.iter().map(graph_link).collect();
+    // levels go from green to red in the order of the group
+    let levels = site.difficulty.as_ref().map_or(&[][..], |g| g.tags.as_slice());
+    let level = func.tags.iter().find_map(|t| levels.iter().position(|l| l == t));
+    let difficulty = level.map(|i| levels[i].as_str());
+    let difficulty_icon = match level {
+        Some(0) => "fas fa-circle text-green",
+        Some(i) if i + 1 == levels.len() => "fas fa-exclamation-triangle text-red",
+        Some(_) => "fas fa-adjust text-orange",
+        None => "fas fa-circle text-gray",
+    };
+    FunctionCardView {
+        function: func,
+        has_manual: func.manual_html.as_ref().is_some_and(|s| !s.is_empty()),
+        has_test_examples: func.examples.iter().any(|e| e.source_file.is_some()),
+        manual_id: sanitize_id(&format!("manual-{}", func.name)),
+        difficulty: difficulty.unwrap_or("unknown").to_string(),
+        difficulty_icon: difficulty_icon.to_string(),
+        location: location(&func.source_file, func.line),
+        include: func.header.as_ref().map(|path| IncludeView {
+            directive: format!("#include \"{}\"", site.header_names.get(path).map_or(path.as_str(), |n| n.as_str())),
+            href: site.header_hrefs.get(path).cloned(),
+            via: headers::umbrellas(site.metadata, path).into_iter()
+                .map(|umbrella| NameLink {
+                    name: site.header_names.get(umbrella).cloned().unwrap_or_else(|| umbrella.to_string()),
+                    href: site.header_hrefs.get(umbrella).cloned(),
+                })
+                .collect(),
+        }),
+        tags: func.tags.iter()
+            .map(|t| TagView { name: t.clone(), class: sanitize_tag_class(t) })
+            .collect(),
+        href: site.href(func),
+        prototype_html: link_types(&func.prototype, type_hrefs),
+        manual_html: func.manual_html.as_deref()
+            .filter(|html| !html.is_empty())
+            .map(highlight::highlight_code_blocks),
+        call_graph_svg: (!callers.is_empty() || !callees.is_empty()).then(|| {
+            call_graph::neighbourhood_svg(&sanitize_id(&format!("graph-{}", func.name)), &func.name, &callers, &callees)
+        }),
+        calls: callees.into_iter().map(NameLink::from).collect(),
+        called_by: callers.into_iter().map(NameLink::from).collect(),
+        see_also: func.see_also.iter()
+            .map(|name| {
+                let name = name.trim_end_matches("()");
+                NameLink { name: name.to_string(), href: site.function_link(name, func) }
+            })
+            .collect(),
+        examples: func.examples.iter()
+            .map(|e| ExampleView {
+                title: e.title.clone(),
+                code_html: highlight::highlight_c(&e.code),
+                output: e.output.clone(),
+                source: e.source_file.as_ref().map(|file| match e.line {
+                    Some(line) => format!("{}:{}", file, line),
+                    None => file.clone(),
+                }),
+            })
+            .collect(),
+    }
+}
+
+// pick a stylesheet to copy into the output dir as styles.css
+fn find_css_file() -> Option<PathBuf> {
+    let root = env!("CARGO_MANIFEST_DIR");
+    let candidates = [
+        "static/scss/main.css", // preferred: compiled SCSS
+        "static/styles.css",    // legacy CSS
+        "styles.css",           // repo root (fallback)
+    ];
+    for rel in candidates {
+        let path = Path::new(root).join(rel);
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+    None
+}
+
+pub fn copy_stylesheet(output: &Path) -> anyhow::Result<()> {
+    let dest = output.join("styles.css");
+    if let Some(src) = find_css_file() {
+        fs::copy(src, &dest)?;
+    } else {
+        // ensure the file exists to avoid 404s
+        fs::write(&dest, "/* styles not found */")?;
+    }
+    Ok(())
+}
+
+/// One output file, relative to the output directory.
+pub struct RenderedPage {
+    pub path: PathBuf,
+    pub html: String,
+}
+
+/// Render the whole site: a single `index.html` with hash routing, or an
+/// overview plus one page per category and per function.
+pub fn render_site(
+    metadata: &LibraryMetadata,
+    options: &BuildOptions,
+    theme: &Theme,
+) -> anyhow::Result<Vec<RenderedPage>> {
+    let links = Links { single_page: options.single_page };
+    let site = Site::new(metadata, options.include_internal, options.offline, links, tags::groups(&options.config.tags));
+
+    if options.single_page {
+        let mut ctx = site.context("", None);
+        ctx.sections = site.grouped.keys().map(|path| site.section(path)).collect();
+        ctx.headers = site.metadata.headers.iter().map(|h| header_view(h, &site)).collect();
+        ctx.include_graph_svg = site.include_graph_svg(None);
+        return Ok(vec![RenderedPage { path: PathBuf::from("index.html"), html: theme.render("index", &ctx)? }]);
+    }
+
+    let mut pages = vec![RenderedPage {
+        path: PathBuf::from("index.html"),
+        html: theme.render("home", &site.context("", None))?,
+    }];
+
+    // every tree node gets a page, intermediate directories included
+    for path in site.tree_structure.keys() {
+        let dir = category_dir(path);
+        let mut ctx = site.context(&root_prefix(&dir), Some(path.clone()));
+        let prefix = format!("{}/", path);
+        ctx.sections = site.grouped.keys()
+            .filter(|p| *p == path || p.starts_with(&prefix))
+            .map(|p| site.section(p))
+            .collect();
+        ctx.category = Some(path.clone());
+        pages.push(RenderedPage { path: Path::new(&dir).join("index.html"), html: theme.render("category", &ctx)? });
+    }
+
+    if !site.metadata.headers.is_empty() {
+        let mut ctx = site.context("../", Some("Headers".to_string()));
+        ctx.headers = site.metadata.headers.iter().map(|h| header_view(h, &site)).collect();
+        ctx.include_graph_svg = site.include_graph_svg(None);
+        pages.push(RenderedPage { path: PathBuf::from("headers/index.html"), html: theme.render("headers", &ctx)? });
+    }
+    for header in &site.metadata.headers {
+        let mut ctx = site.context("../", Some(header.name.clone()));
+        ctx.header = Some(header_view(header, &site));
+        ctx.include_graph_svg = site.include_graph_svg(Some(&header.path));
+        pages.push(RenderedPage { path: PathBuf::from(format!("headers/{}.html", slug(&header.path))), html: theme.render("header-file", &ctx)? });
+    }
+
+    for funcs in site.grouped.values() {
+        for func in funcs {
+            let mut ctx = site.context("../", Some(func.name.clone()));
+            ctx.breadcrumb = breadcrumb(&category_key(&func.category, &func.category_path), site.links);
+            ctx.function = Some(function_card_view(func, &site));
+            pages.push(RenderedPage {
+                path: PathBuf::from(site.href(func)),
+                html: theme.render("function", &ctx)?,
+            });
+        }
+    }
+    Ok(pages)
+}
+
+// `categories/a/b` -> `../../../`
+fn root_prefix(dir: &str) -> String {
+    "../".repeat(dir.split('/').count())
+}
+
+fn breadcrumb(path: &str, links: Links) -> Vec<Crumb> {
+    let parts: Vec<&str> = path.split('/').collect();
+    parts.iter().enumerate()
+        .map(|(i, part)| Crumb { name: part.to_string(), href: links.category(&parts[..=i].join("/")) })
+        .collect()
+}
+
+// Everything the pages share, computed once per build
+struct Site<'a> {
+    metadata: &'a LibraryMetadata,
+    links: Links,
+    offline: bool,
+    function_count: usize,
+    grouped: BTreeMap<String, Vec<&'a FunctionMetadata>>,
+    type_groups: BTreeMap<String, Vec<TypeCard<'a>>>,
+    type_hrefs: HashMap<String, String>,
+    // listed functions only: by name, public ones first, and static helpers
+    // by file and name
+    function_hrefs: HashMap<String, String>,
+    static_hrefs: HashMap<(String, String), String>,
+    // header path -> page, and -> file name
+    header_hrefs: HashMap<String, String>,
+    header_names: HashMap<String, String>,
+    include_graph: headers::IncludeGraph,
+    // the `difficulty` tag group, shown on the cards
+    difficulty: Option<TagGroup>,
+    filters: Vec<FilterGroupView>,
+    tree_structure: BTreeMap<String, TreeNode>,
+    categories: Vec<CategoryView>,
+    tree: Vec<TreeNodeView>,
+}
+
+impl<'a> Site<'a> {
+    fn new(metadata: &'a LibraryMetadata, include_internal: bool, offline: bool, links: Links, tag_groups: Vec<TagGroup>) -> Self {
+        let functions = listed_functions(metadata, include_internal);
+
+        let mut grouped = group_functions_by_path(&functions, &metadata.order);
+        // Paths declaring only types still get a section and a tree node
+        let type_groups = group_types_by_path(metadata);
+        for path in type_groups.keys() {
+            grouped.entry(path.clone()).or_default();
+        }
+
+        // Categories with function counts
+        let categories = metadata.categories.iter()
+            .map(|category| CategoryView {
+                name: category.clone(),
+                count: functions.iter().filter(|f| f.category == *category).count(),
+                href: links.category(category),
+            })
+            .collect();
+
+        // Build tree structure for better navigation
+        let tree_structure = build_tree_structure(&grouped);
+        let tree = tree_structure.keys()
+            .filter(|k| !k.contains('/'))
+            .map(|root| tree_node_view(root, &tree_structure, &grouped, &type_groups, links, 0))
+            .collect();
+
+        let mut by_name = HashMap::new();
+        let mut by_file = HashMap::new();
+        for (func, href) in functions.iter().zip(function_hrefs(&functions, links.single_page)) {
+            if func.internal {
+                by_file.insert((func.source_file.clone().unwrap_or_default(), func.name.clone()), href.clone());
+            }
+            by_name.entry(func.name.clone()).or_insert(href);
+        }
+
+        Self {
+            metadata,
+            links,
+            offline,
+            function_count: functions.len(),
+            type_hrefs: type_links(metadata, links),
+            function_hrefs: by_name,
+            static_hrefs: by_file,
+            header_hrefs: metadata.headers.iter().map(|h| (h.path.clone(), links.header(&h.path))).collect(),
+            header_names: metadata.headers.iter().map(|h| (h.path.clone(), h.name.clone())).collect(),
+            include_graph: headers::graph(metadata),
+            filters: tag_groups.iter()
+                .map(|group| FilterGroupView {
+                    label: group.label.clone().unwrap_or_else(|| title_case(&group.name)),
+                    tags: group.tags.iter().map(|tag| FilterView { tag: tag.clone(), label: title_case(tag) }).collect(),
+                })
+                .collect(),
+            difficulty: tag_groups.into_iter().find(|group| group.name == tags::DIFFICULTY_GROUP),
+            grouped,
+            type_groups,
+            tree_structure,
+            categories,
+            tree,
+        }
+    }
+
+    fn context(&self, root: &str, page_title: Option<String>) -> PageContext<'_> {
+        PageContext {
+            library: LibraryView {
+                name: &self.metadata.name,
+                version: &self.metadata.version,
+                description: &self.metadata.description,
+            },
+            single_page: self.links.single_page,
+            offline: self.offline,
+            root: root.to_string(),
+            home_href: self.links.home(),
+            page_title,
+            function_count: self.function_count,
+            category_count: self.metadata.categories.len(),
+            header_count: self.metadata.headers.len(),
+            headers_href: self.links.headers(),
+            filters: &self.filters,
+            categories: &self.categories,
+            tree: &self.tree,
+            sections: Vec::new(),
+            category: None,
+            breadcrumb: Vec::new(),
+            function: None,
+            headers: Vec::new(),
+            header: None,
+            include_graph_svg: None,
+        }
+    }
+
+    // The page of a listed function
+    fn href(&self, func: &FunctionMetadata) -> String {
+        self.function_link(&func.name, func).unwrap_or_default()
+    }
+
+    // The page of `name` as `from` sees it: a static helper of its own file
+    // before any other function of that name
+    fn function_link(&self, name: &str, from: &FunctionMetadata) -> Option<String> {
+        let file = from.source_file.clone().unwrap_or_default();
+        self.static_hrefs.get(&(file, name.to_string()))
+            .or_else(|| self.function_hrefs.get(name))
+            .cloned()
+    }
+
+    // The whole include graph, `current` highlighted on its header's page
+    fn include_graph_svg(&self, current: Option<&str>) -> Option<String> {
+        (!self.include_graph.nodes.is_empty())
+            .then(|| self.include_graph.to_svg("include-graph", &self.header_hrefs, current))
+    }
+
+    // Function cards, then types and macros, of one category path
+    fn section(&self, path: &str) -> SectionView<'_> {
+        SectionView {
+            id: sanitize_id(&format!("cat-{}", path)),
+            path: path.to_string(),
+            icon: if path.contains('/') { "fas fa-folder-open" } else { "fas fa-file-code" }.to_string(),
+            breadcrumb: breadcrumb(path, self.links),
+            functions: self.grouped.get(path)
+                .map(|funcs| funcs.iter().map(|f| function_card_view(f, self)).collect())
+                .unwrap_or_default(),
+            types: self.type_groups.get(path)
+                .map(|cards| cards.iter().map(|c| type_card_view(c, &self.type_hrefs)).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// Helper functions for tree structure
+fn build_tree_structure(grouped: &BTreeMap<String, Vec<&FunctionMetadata>>) -> BTreeMap<String, TreeNode> {
+	let mut tree = BTreeMap::new();
+	
+	for path in grouped.keys() {
+		let parts: Vec<&str> = path.split('/').collect();
+		let mut current_path = String::new();
+		
+		for (i, part) in parts.iter().enumerate() {
+			if i == 0 {
+				current_path = part.to_string();
+			} else {
+				current_path = format!("{}/{}", current_path, part);
+			}
+			
+			tree.entry(current_path.clone()).or_insert_with(|| TreeNode {
+				name: part.to_string(),
+				is_leaf: i == parts.len() - 1,
+			});
+		}
+	}
+	
+	tree
+}
+
+#[derive(Debug)]
+struct TreeNode {
+	name: String,
+	is_leaf: bool,
+}
+
+fn tree_node_view(
+	path: &str,
+	tree: &BTreeMap<String, TreeNode>,
+	grouped: &BTreeMap<String, Vec<&FunctionMetadata>>,
+	types: &BTreeMap<String, Vec<TypeCard>>,
+	links: Links,
+	depth: usize,
+) -> TreeNodeView {
+	let node = tree.get(path).unwrap();
+
+	// Add children
+	let children = tree.keys()
+		.filter(|k| k.starts_with(&format!("{}/", path)) && k.matches('/').count() == path.matches('/').count() + 1)
+		.map(|child| tree_node_view(child, tree, grouped, types, links, depth + 1))
+		.collect();
+
+	TreeNodeView {
+		name: node.name.clone(),
+		path: path.to_string(),
+		href: links.category(path),
+		depth,
+		icon: if node.is_leaf { "fas fa-file-code" } else { "fas fa-folder" }.to_string(),
+		count: grouped.get(path).map(|v| v.len()).unwrap_or(0),
+		// Types and macros declared for this category
+		types: types.get(path)
+			.map(|cards| cards.iter()
+				.map(|c| TypeLinkView {
+					name: c.name().to_string(),
+					icon: c.icon().to_string(),
+					href: links.type_card(c.name(), path),
+				})
+				.collect())
+			.unwrap_or_default(),
+		children,
+	}
+}
+
+// --- Template context ---
+
+#[derive(Serialize)]
+struct PageContext<'a> {
+	library: LibraryView<'a>,
+	single_page: bool,
+	offline: bool,
+	// relative path back to the output root, for `<base href>`
+	root: String,
+	home_href: String,
+	page_title: Option<String>,
+	function_count: usize,
+	category_count: usize,
+	header_count: usize,
+	headers_href: String,
+	// one section of the filter bar per tag group
+	filters: &'a [FilterGroupView],
+	categories: &'a [CategoryView],
+	tree: &'a [TreeNodeView],
+	sections: Vec<SectionView<'a>>,
+	// category page: its path
+	category: Option<String>,
+	// function page: where it sits, and its card
+	breadcrumb: Vec<Crumb>,
+	function: Option<FunctionCardView<'a>>,
+	// headers index (and the single page): every header; header page: its card
+	headers: Vec<HeaderView>,
+	header: Option<HeaderView>,
+	include_graph_svg: Option<String>,
+}
+
+// What the templates show of the library, not its every function
+#[derive(Serialize)]
+struct LibraryView<'a> {
+	name: &'a str,
+	version: &'a str,
+	description: &'a str,
+}
+
+#[derive(Serialize)]
+struct FilterGroupView {
+	label: String,
+	tags: Vec<FilterView>,
+}
+
+#[derive(Serialize)]
+struct FilterView {
+	tag: String,
+	label: String,
+}
+
+#[derive(Serialize)]
+struct CategoryView {
+	name: String,
+	count: usize,
+	href: String,
+}
+
+#[derive(Serialize)]
+struct TreeNodeView {
+	name: String,
+	path: String,
+	href: String,
+	depth: usize,
+	icon: String,
+	count: usize,
+	types: Vec<TypeLinkView>,
+	children: Vec<TreeNodeView>,
+}
+
+#[derive(Serialize)]
+struct TypeLinkView {
+	name: String,
+	icon: String,
+	href: String,
+}
+
+#[derive(Serialize)]
+struct SectionView<'a> {
+	id: String,
+	path: String,
+	icon: String,
+	breadcrumb: Vec<Crumb>,
+	functions: Vec<FunctionCardView<'a>>,
+	types: Vec<TypeCardView>,
+}
+
+#[derive(Serialize)]
+struct Crumb {
+	name: String,
+	href: String,
+}
+
+#[derive(Serialize)]
+struct FunctionCardView<'a> {
+	function: &'a FunctionMetadata,
+	has_manual: bool,
+	// shown under a manual, which replaces the generated examples
+	has_test_examples: bool,
+	manual_id: String,
+	difficulty: String,
+	difficulty_icon: String,
+	location: Option<String>,
+	// `#include "libft.h"`, linked to the header's page
+	include: Option<IncludeView>,
+	tags: Vec<TagView>,
+	// full documentation: hash route or page
+	href: String,
+	// prototype highlighted, with documented types linked
+	prototype_html: String,
+	// manual with its code blocks highlighted
+	manual_html: Option<String>,
+	examples: Vec<ExampleView>,
+	// callers left, callees right; see `call_graph::neighbourhood_svg`
+	call_graph_svg: Option<String>,
+	calls: Vec<NameLink>,
+	called_by: Vec<NameLink>,
+	see_also: Vec<NameLink>,
+}
+
+// A function name, linked when it has a page
+#[derive(Serialize)]
+struct NameLink {
+	name: String,
+	href: Option<String>,
+}
+
+impl From<call_graph::GraphLink> for NameLink {
+	fn from(link: call_graph::GraphLink) -> Self {
+		NameLink { name: link.name, href: link.href }
+	}
+}
+
+#[derive(Serialize)]
+struct HeaderView {
+	id: String,
+	name: String,
+	path: String,
+	href: String,
+	guard: Option<String>,
+	includes: Vec<IncludeView>,
+	included_by: Vec<NameLink>,
+	functions: Vec<NameLink>,
+	types: Vec<NameLink>,
+	macros: Vec<String>,
+}
+
+// An `#include` line, linked when it names a library header
+#[derive(Serialize)]
+struct IncludeView {
+	directive: String,
+	href: Option<String>,
+	// umbrella headers that include it too
+	via: Vec<NameLink>,
+}
+
+#[derive(Serialize)]
+struct ExampleView {
+	title: String,
+	code_html: String,
+	output: Option<String>,
+	// `tests/test_str.c:12` for examples harvested from the tests
+	source: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TagView {
+	name: String,
+	class: String,
+}
+
+#[derive(Serialize)]
+struct TypeCardView {
+	id: String,
+	name: String,
+	kind: String,
+	icon: String,
+	description: String,
+	location: Option<String>,
+	definition_html: String,
+	members: Vec<MemberView>,
+}
+
+#[derive(Serialize)]
+struct MemberView {
+	name: String,
+	description: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILES: &[(&str, &str)] = &[
+        ("include/libft.h", "#ifndef LIBFT_H\n# define LIBFT_H\n\ntypedef struct s_pair\n{\n\tint\ta;\n}\tt_pair;\n\nint\tft_a(t_pair *p);\nint\tft_b(int x);\n\n#endif\n"),
+        ("str/ft_a.c", "static int\thelper(int x)\n{\n\treturn (x);\n}\n\nint\tft_a(t_pair *p)\n{\n\treturn (helper(p->a));\n}\n"),
+        ("str/deep/ft_b.c", "static int\thelper(int x)\n{\n\treturn (x);\n}\n\nint\tft_b(int x)\n{\n\treturn (helper(x));\n}\n"),
+    ];
+
+    fn metadata(name: &str) -> LibraryMetadata {
+        let dir = std::env::temp_dir().join(format!("libft-docs-renderer-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (file, content) in FILES {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        let metadata = LibftParser::new(dir.to_string_lossy().to_string()).parse().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        metadata
+    }
+
+    fn page_paths(metadata: &LibraryMetadata, single_page: bool) -> Vec<String> {
+        let options = BuildOptions { single_page, include_internal: true, ..BuildOptions::default() };
+        let theme = Theme::load(None).unwrap();
+        let mut paths: Vec<String> = render_site(metadata, &options, &theme).unwrap().iter()
+            .map(|p| p.path.to_string_lossy().to_string())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn every_function_category_and_header_gets_a_page() {
+        let metadata = metadata("pages");
+        assert_eq!(page_paths(&metadata, false), [
+            "categories/include/index.html",
+            "categories/str/deep/index.html",
+            "categories/str/index.html",
+            "functions/ft_a.html",
+            "functions/ft_b.html",
+            "functions/helper--str_deep_ft_b.c.html",
+            "functions/helper--str_ft_a.c.html",
+            "headers/include_libft.h.html",
+            "headers/index.html",
+            "index.html",
+        ]);
+        assert_eq!(page_paths(&metadata, true), ["index.html"]);
+    }
+
+    #[test]
+    fn helpers_sharing_a_name_get_their_file_in_the_href() {
+        let metadata = metadata("hrefs");
+        let listed = listed_functions(&metadata, false);
+        assert!(function_hrefs(&listed, false).iter().all(|h| !h.contains("--")));
+        let mut listed = listed_functions(&metadata, true);
+        listed.sort_by(|a, b| a.name.cmp(&b.name).then(a.source_file.cmp(&b.source_file)));
+        assert_eq!(function_hrefs(&listed, true)[2], "#/function/helper");
+        assert_eq!(function_hrefs(&listed, false)[2], "functions/helper--str_deep_ft_b.c.html");
+    }
+
+    #[test]
+    fn documented_types_are_linked_in_code() {
+        let metadata = metadata("types");
+        let links = type_links(&metadata, Links { single_page: false });
+        let html = link_types("int\tft_a(t_pair *p);", &links);
+        assert!(html.contains("<a class=\"type-link\" href=\"categories/include/index.html#type-t-pair\">t_pair</a>"), "{}", html);
+        assert!(!link_types("int\tx;", &links).contains("type-link"));
+    }
+
+    #[test]
+    fn page_names_are_file_safe() {
+        assert_eq!(slug("ft_str/join"), "ft_str_join");
+        assert_eq!(slug(".."), "_");
+        assert_eq!(category_dir("data structures/vec"), "categories/data_structures/vec");
+        assert_eq!(root_prefix("categories/a/b"), "../../../");
+        let crumbs: Vec<_> = breadcrumb("a/b", Links { single_page: true }).into_iter().map(|c| c.href).collect();
+        assert_eq!(crumbs, ["#/category/a", "#/category/a/b"]);
+    }
+}
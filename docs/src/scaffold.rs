@@ -0,0 +1,208 @@
+//! Manual stubs for functions documented only by their source: a JSON
+//! manual pre-filled from the parsed metadata, pointing at a markdown
+//! skeleton to complete. Existing files are never touched.
+
+use crate::{renderer, Example, FunctionMetadata, LibraryMetadata, Parameter, NO_DESCRIPTION, NO_RETURN_VALUE, PLACEHOLDER_EXAMPLE};
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// What a scaffold run wrote and left alone.
+#[derive(Debug, Default)]
+pub struct ScaffoldSummary {
+    pub written: Vec<PathBuf>,
+    pub skipped: Vec<PathBuf>,
+}
+
+// Same shape as the manuals `manual::load_manual` reads
+#[derive(Serialize)]
+struct ManualStub<'a> {
+    name: &'a str,
+    category: &'a str,
+    category_path: &'a str,
+    prototype: &'a str,
+    description: &'a str,
+    parameters: &'a [Parameter],
+    return_value: &'a str,
+    examples: Vec<&'a Example>,
+    tags: &'a [String],
+    see_also: &'a [String],
+    manual_path: String,
+    // a `static` helper: the loader documents the one of this file
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    internal: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_file: Option<&'a str>,
+}
+
+/// Write `<name>.json` and `<name>.md` into `dir` for every listed function
+/// without a manual, sorted by name.
+pub fn scaffold(metadata: &LibraryMetadata, dir: &Path, include_internal: bool) -> anyhow::Result<ScaffoldSummary> {
+    let mut functions: Vec<&FunctionMetadata> = renderer::listed_functions(metadata, include_internal)
+        .into_iter()
+        .filter(|f| !f.has_manual)
+        .collect();
+    functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+    fs::create_dir_all(dir)?;
+    let mut summary = ScaffoldSummary::default();
+    for func in functions {
+        let json_path = dir.join(format!("{}.json", func.name));
+        let md_name = format!("{}.md", func.name);
+        // a JSON manual is there already: a markdown page alone would be stray
+        if json_path.exists() {
+            summary.skipped.push(json_path);
+            continue;
+        }
+        let stub = ManualStub {
+            name: &func.name,
+            category: &func.category,
+            category_path: &func.category_path,
+            prototype: &func.prototype,
+            description: &func.description,
+            parameters: &func.parameters,
+            return_value: &func.return_value,
+            examples: real_examples(func),
+            tags: &func.tags,
+            see_also: &func.see_also,
+            manual_path: md_name.clone(),
+            internal: func.internal,
+            source_file: func.source_file.as_deref().filter(|_| func.internal),
+        };
+        let json = serde_json::to_string_pretty(&stub)? + "\n";
+        // the markdown first: a JSON stub alone would hide the parsed docs
+        for (path, contents) in [(dir.join(&md_name), markdown_skeleton(func)), (json_path, json)] {
+            if create_new(&path, &contents)? {
+                summary.written.push(path);
+            } else {
+                summary.skipped.push(path);
+            }
+        }
+    }
+    Ok(summary)
+}
+
+fn real_examples(func: &FunctionMetadata) -> Vec<&Example> {
+    // harvested from the tests again on every build
+    func.examples.iter().filter(|e| !e.code.contains(PLACEHOLDER_EXAMPLE) && e.source_file.is_none()).collect()
+}
+
+// false when the file already exists
+fn create_new(path: &Path, contents: &str) -> anyhow::Result<bool> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(mut file) => {
+            file.write_all(contents.as_bytes())?;
+            Ok(true)
+        }
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(err) => Err(anyhow::anyhow!("{}: {}", path.display(), err)),
+    }
+}
+
+fn markdown_skeleton(func: &FunctionMetadata) -> String {
+    let mut md = format!("# {}\n\n## Synopsis\n\n```c\n{};\n```\n\n## Description\n\n", func.name, func.prototype);
+    if func.description.trim().is_empty() || func.description == NO_DESCRIPTION {
+        md.push_str(&format!("TODO: describe what `{}` does.\n", func.name));
+    } else {
+        md.push_str(&format!("{}\n", func.description));
+    }
+    if !func.parameters.is_empty() {
+        md.push_str("\n## Parameters\n\n");
+        for p in &func.parameters {
+            let description = if p.description.trim().is_empty() { "TODO" } else { p.description.as_str() };
+            md.push_str(&format!("- `{}`: {}\n", p.name, description));
+        }
+    }
+
+    md.push_str("\n## Return\n\n");
+    if func.return_value.trim().is_empty() || func.return_value == NO_RETURN_VALUE {
+        md.push_str("TODO: describe the return value.\n");
+    } else {
+        md.push_str(&format!("{}\n", func.return_value));
+    }
+
+    md.push_str("\n## Examples\n\n");
+    let examples = real_examples(func);
+    if examples.is_empty() {
+        md.push_str(&format!("```c\n// TODO: show how to call {}\n```\n", func.name));
+    }
+    for example in examples {
+        md.push_str(&format!("```c\n{}\n```\n", example.code.trim_end()));
+    }
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn library() -> LibraryMetadata {
+        let mut metadata: LibraryMetadata = serde_json::from_value(json!({
+            "name": "libft", "version": "1", "description": "", "author": "", "categories": ["str"], "functions": {},
+            "internal_functions": [{
+                "name": "helper", "category": "str", "prototype": "static int\thelper(int x)",
+                "source_file": "str/ft_a.c", "internal": true,
+            }],
+        })).unwrap();
+        let func: FunctionMetadata = serde_json::from_value(json!({
+            "name": "ft_a", "category": "str", "prototype": "int\tft_a(int x)",
+            "description": NO_DESCRIPTION, "return_value": "x plus one",
+            "parameters": [{ "name": "x", "type_name": "int", "description": "" }],
+            "examples": [
+                { "title": "harvested", "code": "ft_a(1);", "output": null, "source_file": "tests/a.c" },
+                { "title": "written", "code": "ft_a(2);", "output": "3" },
+            ],
+            "source_file": "str/ft_a.c",
+        })).unwrap();
+        metadata.functions.insert(func.name.clone(), func);
+        metadata
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libft-docs-scaffold-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn stubs_keep_written_examples_and_mark_todos() {
+        let func = &library().functions["ft_a"];
+        let md = markdown_skeleton(func);
+        assert!(md.starts_with("# ft_a\n\n## Synopsis\n\n```c\nint\tft_a(int x);\n```\n"));
+        assert!(md.contains("TODO: describe what `ft_a` does."));
+        assert!(md.contains("- `x`: TODO\n"));
+        assert!(md.contains("## Return\n\nx plus one\n"));
+        assert!(md.contains("```c\nft_a(2);\n```") && !md.contains("ft_a(1);"));
+    }
+
+    #[test]
+    fn existing_files_are_left_alone() {
+        let dir = temp_dir("existing");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("ft_a.md"), "mine").unwrap();
+        let summary = scaffold(&library(), &dir, false).unwrap();
+        assert_eq!(summary.written, [dir.join("ft_a.json")]);
+        assert_eq!(summary.skipped, [dir.join("ft_a.md")]);
+        assert_eq!(fs::read_to_string(dir.join("ft_a.md")).unwrap(), "mine");
+
+        let summary = scaffold(&library(), &dir, false).unwrap();
+        assert!(summary.written.is_empty());
+        assert_eq!(summary.skipped, [dir.join("ft_a.json")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn helper_stubs_name_their_file() {
+        let dir = temp_dir("internal");
+        scaffold(&library(), &dir, true).unwrap();
+        let helper: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join("helper.json")).unwrap()).unwrap();
+        assert_eq!(helper["internal"], true);
+        assert_eq!(helper["source_file"], "str/ft_a.c");
+        let public: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join("ft_a.json")).unwrap()).unwrap();
+        assert!(public.get("internal").is_none() && public.get("source_file").is_none());
+        assert_eq!(public["manual_path"], "ft_a.md");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,358 @@
+//! Validation of manual files against `schema/manual.schema.json`.
+//!
+//! Only the JSON Schema keywords the manual schema uses are understood:
+//! `type`, `properties`, `required`, `additionalProperties`, `items`,
+//! `minLength`, `minimum` and local `$ref`s. Every problem is reported at the
+//! line and column of the offending value.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The published manual schema.
+pub const MANUAL_SCHEMA: &str = include_str!("../schema/manual.schema.json");
+
+/// 1-based position in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pos {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Pos {
+    const START: Pos = Pos { line: 1, column: 1 };
+}
+
+#[derive(Debug)]
+pub struct Violation {
+    // JSON pointer of the value, `/parameters/0/name`
+    pub pointer: String,
+    pub pos: Pos,
+    pub message: String,
+    // an unknown property: reported, but the manual still loads
+    pub unknown_property: bool,
+}
+
+/// Positions of every value (and of every object key, as `pointer` + `#key`)
+/// of a syntactically valid JSON document.
+pub fn positions(text: &str) -> HashMap<String, Pos> {
+    let mut locator = Locator { chars: text.chars().collect(), i: 0, line: 1, column: 1, out: HashMap::new() };
+    locator.value(String::new());
+    locator.out
+}
+
+struct Locator {
+    chars: Vec<char>,
+    i: usize,
+    line: usize,
+    column: usize,
+    out: HashMap<String, Pos>,
+}
+
+impl Locator {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.i).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.i += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn pos(&self) -> Pos {
+        Pos { line: self.line, column: self.column }
+    }
+
+    fn value(&mut self, pointer: String) {
+        self.skip_ws();
+        self.out.insert(pointer.clone(), self.pos());
+        match self.peek() {
+            Some('{') => {
+                self.bump();
+                loop {
+                    self.skip_ws();
+                    match self.peek() {
+                        Some('}') | None => break,
+                        Some(',') => {
+                            self.bump();
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    let key_pos = self.pos();
+                    let key = self.string();
+                    let child = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                    self.out.insert(format!("{}#key", child), key_pos);
+                    self.skip_ws();
+                    if self.peek() == Some(':') {
+                        self.bump();
+                    }
+                    self.value(child);
+                }
+                self.bump();
+            }
+            Some('[') => {
+                self.bump();
+                let mut index = 0;
+                loop {
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(']') | None => break,
+                        Some(',') => {
+                            self.bump();
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    self.value(format!("{}/{}", pointer, index));
+                    index += 1;
+                }
+                self.bump();
+            }
+            Some('"') => {
+                self.string();
+            }
+            _ => {
+                while self.peek().is_some_and(|c| !matches!(c, ',' | '}' | ']') && !c.is_whitespace()) {
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let mut s = String::new();
+        self.bump(); // opening quote
+        while let Some(c) = self.bump() {
+            match c {
+                '"' => break,
+                '\\' => {
+                    // keys are only used as pointers; keep escapes verbatim
+                    if let Some(escaped) = self.bump() {
+                        s.push(escaped);
+                    }
+                }
+                c => s.push(c),
+            }
+        }
+        s
+    }
+}
+
+/// Check `value` against the manual schema; `positions` locates violations.
+pub fn validate_manual(value: &Value, positions: &HashMap<String, Pos>) -> Vec<Violation> {
+    validate(value, positions, &[])
+}
+
+/// Like [`validate_manual`], for front matter completing a parsed function:
+/// `name` and `category` may come from the file name and the sources.
+pub fn validate_front_matter(value: &Value, positions: &HashMap<String, Pos>) -> Vec<Violation> {
+    validate(value, positions, &["name", "category"])
+}
+
+fn validate(value: &Value, positions: &HashMap<String, Pos>, optional: &[&str]) -> Vec<Violation> {
+    let schema: Value = serde_json::from_str(MANUAL_SCHEMA).expect("manual schema is valid JSON");
+    let mut violations = Vec::new();
+    Validator { root: &schema, positions, optional, out: &mut violations }.check(value, &schema, "");
+    violations
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    positions: &'a HashMap<String, Pos>,
+    // top-level required properties that may be left out
+    optional: &'a [&'a str],
+    out: &'a mut Vec<Violation>,
+}
+
+impl<'a> Validator<'a> {
+    fn report(&mut self, pointer: &str, message: String, unknown_property: bool) {
+        let pos = self.positions.get(pointer).copied().unwrap_or(Pos::START);
+        self.out.push(Violation { pointer: pointer.to_string(), pos, message, unknown_property });
+    }
+
+    fn resolve<'s>(&self, schema: &'s Value) -> &'s Value
+    where
+        'a: 's,
+    {
+        match schema.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix('#')) {
+            Some(path) => self.root.pointer(path).unwrap_or(schema),
+            None => schema,
+        }
+    }
+
+    fn check(&mut self, value: &Value, schema: &Value, pointer: &str) {
+        let schema = self.resolve(schema);
+        let field = if pointer.is_empty() { "manual".to_string() } else { format!("`{}`", pointer.trim_start_matches('/')) };
+
+        if let Some(expected) = schema.get("type") {
+            let types: Vec<&str> = match expected {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+                self.report(pointer, format!("{} must be {}, found {}", field, types.join(" or "), type_name(value)), false);
+                return;
+            }
+        }
+
+        match value {
+            Value::Object(map) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                let optional: &[&str] = if pointer.is_empty() { self.optional } else { &[] };
+                for name in schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+                    if !map.contains_key(name) && !optional.contains(&name) {
+                        self.report(pointer, format!("{} is missing required property `{}`", field, name), false);
+                    }
+                }
+                let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+                for (key, child) in map {
+                    let child_pointer = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                    match properties.and_then(|p| p.get(key)) {
+                        Some(child_schema) => self.check(child, child_schema, &child_pointer),
+                        None if closed => {
+                            let known: Vec<&str> = properties.map(|p| p.keys().map(String::as_str).collect()).unwrap_or_default();
+                            let hint = closest(key, &known).map(|k| format!(" (did you mean `{}`?)", k)).unwrap_or_default();
+                            self.report(&format!("{}#key", child_pointer),
+                                format!("unknown property `{}` in {}{}", key, field, hint), true);
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        self.check(item, item_schema, &format!("{}/{}", pointer, i));
+                    }
+                }
+            }
+            Value::String(s) => {
+                if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                    if (s.chars().count() as u64) < min {
+                        self.report(pointer, format!("{} must not be empty", field), false);
+                    }
+                }
+            }
+            Value::Number(n) => {
+                if let (Some(min), Some(n)) = (schema.get("minimum").and_then(Value::as_f64), n.as_f64()) {
+                    if n < min {
+                        self.report(pointer, format!("{} must be at least {}", field, min), false);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn has_type(value: &Value, t: &str) -> bool {
+    match t {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+    }
+}
+
+// A known property one or two edits away from a typo
+fn closest<'k>(key: &str, known: &[&'k str]) -> Option<&'k str> {
+    known.iter()
+        .map(|k| (edit_distance(key, k), *k))
+        .filter(|(d, _)| *d <= 2)
+        .min()
+        .map(|(_, k)| k)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            row.push((prev[j] + (ca != *cb) as usize).min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violations(text: &str) -> Vec<Violation> {
+        validate_manual(&serde_json::from_str(text).unwrap(), &positions(text))
+    }
+
+    fn at(line: usize, column: usize) -> Pos {
+        Pos { line, column }
+    }
+
+    #[test]
+    fn values_and_keys_are_located() {
+        let positions = positions("{\n  \"name\": \"ft_a\",\n  \"tags\": [\"x\", \"y\"]\n}");
+        assert_eq!(positions[""], at(1, 1));
+        assert_eq!(positions["/name#key"], at(2, 3));
+        assert_eq!(positions["/name"], at(2, 11));
+        assert_eq!(positions["/tags/1"], at(3, 17));
+    }
+
+    #[test]
+    fn type_errors_point_at_the_value() {
+        let found = violations("{\n  \"name\": \"ft_a\",\n  \"category\": \"str\",\n  \"parameters\": [\n    {\"name\": \"s\", \"type_name\": 1, \"description\": \"\"}\n  ]\n}");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].pointer, "/parameters/0/type_name");
+        assert_eq!(found[0].pos, at(5, 32));
+        assert_eq!(found[0].message, "`parameters/0/type_name` must be string, found number");
+    }
+
+    #[test]
+    fn unknown_properties_point_at_the_key_with_a_hint() {
+        let found = violations("{\"name\": \"ft_a\", \"category\": \"str\",\n \"descripton\": \"x\"}");
+        assert_eq!(found.len(), 1);
+        assert!(found[0].unknown_property);
+        assert_eq!(found[0].pos, at(2, 2));
+        assert_eq!(found[0].message, "unknown property `descripton` in manual (did you mean `description`?)");
+    }
+
+    #[test]
+    fn missing_required_properties_point_at_the_object() {
+        let found = violations("{\"name\": \"ft_a\", \"line\": 0}");
+        let messages: Vec<_> = found.iter().map(|v| (v.pos, v.message.as_str())).collect();
+        assert_eq!(messages, [
+            (at(1, 1), "manual is missing required property `category`"),
+            (at(1, 26), "`line` must be at least 1"),
+        ]);
+        let value = serde_json::json!({ "description": "x" });
+        assert!(validate_front_matter(&value, &HashMap::new()).is_empty());
+    }
+}
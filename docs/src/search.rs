@@ -0,0 +1,255 @@
+//! Prebuilt client search index (`search-index.json`).
+//!
+//! Every field is tokenized and stemmed at build time; the page only stems
+//! the query with the same rules (see `scripts.hbs`) and ranks functions by
+//! summed term weights, with prefix and typo tolerance.
+
+use crate::{escape, renderer, FunctionMetadata, LibraryMetadata, SearchIndex, SearchableFunction};
+use std::collections::{BTreeMap, BTreeSet};
+
+// How much a term counts depending on where it was found
+const NAME_WEIGHT: u32 = 10;
+const NAME_PART_WEIGHT: u32 = 6;
+const TAG_WEIGHT: u32 = 4;
+const DESCRIPTION_WEIGHT: u32 = 3;
+const PARAMETER_WEIGHT: u32 = 2;
+const MANUAL_WEIGHT: u32 = 1;
+
+// libft naming shorthand and the words it stands for, so that
+// "duplicate string" finds `ft_strdup`
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("alnum", "alphanumeric"),
+    ("alpha", "alphabetic"),
+    ("arr", "array"),
+    ("atoi", "integer"),
+    ("buf", "buffer"),
+    ("cat", "concatenate"),
+    ("chr", "character"),
+    ("cmp", "compare"),
+    ("cpy", "copy"),
+    ("del", "delete"),
+    ("dup", "duplicate"),
+    ("fd", "descriptor"),
+    ("int", "integer"),
+    ("iter", "iterate"),
+    ("itoa", "integer"),
+    ("len", "length"),
+    ("lst", "list"),
+    ("mem", "memory"),
+    ("nbr", "number"),
+    ("num", "number"),
+    ("put", "write"),
+    ("str", "string"),
+    ("tolower", "lowercase"),
+    ("toupper", "uppercase"),
+    ("vec", "vector"),
+];
+
+// Too common to tell functions apart; `scripts.hbs` drops them from queries too
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "into", "is",
+    "it", "of", "on", "or", "the", "to", "with",
+];
+
+/// Index every listed function. `href`s follow the site layout, relative
+/// to the output root.
+pub fn build_index(metadata: &LibraryMetadata, include_internal: bool, single_page: bool) -> SearchIndex {
+    let listed = renderer::listed_functions(metadata, include_internal);
+    let mut functions: Vec<SearchableFunction> = listed.iter()
+        .zip(renderer::function_hrefs(&listed, single_page))
+        .map(|(func, href)| searchable(func, href))
+        .collect();
+    functions.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.category.cmp(&b.category)));
+
+    let tags: BTreeSet<String> = functions.iter().flat_map(|f| f.tags.iter().cloned()).collect();
+    SearchIndex {
+        functions,
+        categories: metadata.categories.clone(),
+        tags: tags.into_iter().collect(),
+    }
+}
+
+fn searchable(func: &FunctionMetadata, href: String) -> SearchableFunction {
+    let mut terms: BTreeMap<String, u32> = BTreeMap::new();
+    // a field counts once per term, however often the term appears in it
+    let mut add = |words: Vec<String>, weight: u32| {
+        let unique: BTreeSet<String> = words.iter()
+            .filter(|w| !STOPWORDS.contains(&w.as_str()))
+            .map(|w| stem(w))
+            .collect();
+        for term in unique {
+            *terms.entry(term).or_default() += weight;
+        }
+    };
+
+    let keywords = name_keywords(&func.name);
+    add(vec![func.name.to_lowercase()], NAME_WEIGHT);
+    add(keywords.clone(), NAME_PART_WEIGHT);
+    add(func.tags.iter().flat_map(|t| tokenize(t)).collect(), TAG_WEIGHT);
+    add(tokenize(&func.description), DESCRIPTION_WEIGHT);
+    add(func.parameters.iter()
+        .flat_map(|p| tokenize(&format!("{} {} {}", p.name, p.type_name, p.description)))
+        .collect(), PARAMETER_WEIGHT);
+    if let Some(manual) = &func.manual_html {
+        add(tokenize(&text_content(manual)), MANUAL_WEIGHT);
+    }
+
+    SearchableFunction {
+        name: func.name.clone(),
+        category: if func.category_path.is_empty() { func.category.clone() } else { func.category_path.clone() },
+        tags: func.tags.clone(),
+        description: func.description.clone(),
+        keywords,
+        href,
+        terms,
+    }
+}
+
+/// Lowercase words of `text`; identifiers are also split on `_`.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for word in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        let word = word.trim_matches('_').to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+        if word.contains('_') {
+            words.extend(word.split('_').filter(|w| !w.is_empty()).map(str::to_string));
+        }
+        words.push(word);
+    }
+    words
+}
+
+// `ft_strdup` -> strdup, str, dup, string, duplicate
+fn name_keywords(name: &str) -> Vec<String> {
+    let mut keywords = Vec::new();
+    let lower = name.to_lowercase();
+    for part in lower.split('_').filter(|p| !p.is_empty() && *p != "ft") {
+        keywords.push(part.to_string());
+        let pieces = split_abbreviations(part);
+        if pieces.len() > 1 {
+            keywords.extend(pieces.iter().map(|p| p.to_string()));
+        }
+        for piece in pieces {
+            if let Some((_, word)) = ABBREVIATIONS.iter().find(|(abbr, _)| *abbr == piece) {
+                keywords.push(word.to_string());
+            }
+        }
+    }
+    let mut seen = BTreeSet::new();
+    keywords.retain(|k| seen.insert(k.clone()));
+    keywords
+}
+
+// Split `strdup` into known abbreviations, longest first; the whole word
+// when it does not decompose completely
+fn split_abbreviations(word: &str) -> Vec<&str> {
+    fn split<'a>(word: &'a str, out: &mut Vec<&'a str>) -> bool {
+        if word.is_empty() {
+            return true;
+        }
+        let mut candidates: Vec<&str> = ABBREVIATIONS.iter()
+            .map(|(abbr, _)| *abbr)
+            .filter(|abbr| word.starts_with(abbr))
+            .collect();
+        candidates.sort_by_key(|abbr| std::cmp::Reverse(abbr.len()));
+        for abbr in candidates {
+            out.push(&word[..abbr.len()]);
+            if split(&word[abbr.len()..], out) {
+                return true;
+            }
+            out.pop();
+        }
+        false
+    }
+    let mut out = Vec::new();
+    if split(word, &mut out) { out } else { vec![word] }
+}
+
+/// A light suffix stemmer: plurals, -ing/-ed/-ation/-ly and a final `e`,
+/// keeping at least four letters. Mirrored by `stem()` in `scripts.hbs`.
+pub fn stem(word: &str) -> String {
+    let mut w = word.to_lowercase();
+    if w.len() <= 3 || !w.is_ascii() {
+        return w;
+    }
+    if let Some(s) = w.strip_suffix("ies") {
+        w = format!("{}y", s);
+    } else if w.ends_with("sses") {
+        w.truncate(w.len() - 2);
+    } else if w.ends_with('s') && !w.ends_with("ss") && !w.ends_with("us") && !w.ends_with("is") {
+        w.truncate(w.len() - 1);
+    }
+    for (suffix, replacement) in [("ation", "ate"), ("ing", ""), ("ed", ""), ("ly", "")] {
+        if let Some(s) = w.strip_suffix(suffix) {
+            if s.len() >= 4 {
+                w = format!("{}{}", s, replacement);
+            }
+            break;
+        }
+    }
+    if w.len() > 4 && w.ends_with('e') {
+        w.truncate(w.len() - 1);
+    }
+    w
+}
+
+// Visible text of sanitized manual HTML
+fn text_content(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    escape::unescape_html(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_expand_to_their_abbreviations() {
+        assert_eq!(name_keywords("ft_strdup"), ["strdup", "str", "dup", "string", "duplicate"]);
+        assert_eq!(name_keywords("ft_lstadd_back"), ["lstadd", "back"]);
+        assert_eq!(name_keywords("ft_putnbr_fd"), ["putnbr", "put", "nbr", "write", "number", "fd", "descriptor"]);
+    }
+
+    #[test]
+    fn stemming_matches_the_forms_of_a_word() {
+        assert_eq!(stem("copies"), "copy");
+        assert_eq!(stem("strings"), "string");
+        assert_eq!(stem("sorted"), stem("sorting"));
+        assert_eq!(stem("duplicates"), stem("duplicate"));
+        assert_eq!(stem("fd"), "fd");
+    }
+
+    #[test]
+    fn identifiers_are_split_on_underscores() {
+        assert_eq!(tokenize("Frees `t_list *lst`, see ft_lstclear."),
+            ["frees", "t", "list", "t_list", "lst", "see", "ft", "lstclear", "ft_lstclear"]);
+    }
+
+    #[test]
+    fn terms_are_weighted_by_field_once_each() {
+        let func: FunctionMetadata = serde_json::from_value(serde_json::json!({
+            "name": "ft_strdup", "category": "str", "tags": ["string"],
+            "description": "Duplicates a string, the string given.",
+            "manual_html": "<p>Uses <code>malloc</code>.</p>",
+        })).unwrap();
+        let indexed = searchable(&func, "functions/ft_strdup.html".to_string());
+        assert_eq!(indexed.terms["ft_strdup"], NAME_WEIGHT);
+        assert_eq!(indexed.terms["string"], NAME_PART_WEIGHT + TAG_WEIGHT + DESCRIPTION_WEIGHT);
+        assert_eq!(indexed.terms["malloc"], MANUAL_WEIGHT);
+        assert!(!indexed.terms.contains_key("the"));
+    }
+}
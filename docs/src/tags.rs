@@ -0,0 +1,256 @@
+//! Tags from declarative rules: the built-in ones of `tags/default.toml`
+//! and those of `docs.toml`. A rule matches on the function name, the words
+//! of its body, its header, its category and the tags it already has;
+//! groups keep one tag each (the difficulty level) and make the filter bar.
+
+use crate::config::{self, TagGroup, TagRule, Tags};
+use crate::FunctionMetadata;
+use globset::GlobSet;
+use std::collections::HashSet;
+
+const BUILTIN: &str = include_str!("../tags/default.toml");
+
+/// The group whose tags are the difficulty levels shown on the cards, easiest
+/// first.
+pub const DIFFICULTY_GROUP: &str = "difficulty";
+
+struct Rule {
+    tag: String,
+    priority: i32,
+    names: Option<GlobSet>,
+    body: Option<GlobSet>,
+    headers: Option<GlobSet>,
+    categories: Option<GlobSet>,
+    tags: Vec<String>,
+    recursive: bool,
+}
+
+/// The compiled rules and groups.
+pub struct Tagger {
+    // highest priority first, declaration order within a priority
+    rules: Vec<Rule>,
+    groups: Vec<TagGroup>,
+}
+
+/// The groups in effect: the configured ones, then the built-in ones they
+/// do not replace by name.
+pub fn groups(tags: &Tags) -> Vec<TagGroup> {
+    merged(tags).1
+}
+
+fn merged(tags: &Tags) -> (Vec<TagRule>, Vec<TagGroup>) {
+    let mut rules = Vec::new();
+    let mut groups = tags.groups.clone();
+    if tags.builtin {
+        let builtin: Tags = toml::from_str(BUILTIN).expect("tags/default.toml is valid");
+        rules = builtin.rules;
+        groups.extend(builtin.groups.into_iter().filter(|b| !tags.groups.iter().any(|g| g.name == b.name)));
+    }
+    rules.extend(tags.rules.iter().cloned());
+    (rules, groups)
+}
+
+impl Tagger {
+    pub fn new(tags: &Tags) -> anyhow::Result<Self> {
+        let (rules, groups) = merged(tags);
+        let mut grouped = HashSet::new();
+        for group in &groups {
+            for tag in &group.tags {
+                if !grouped.insert(tag.as_str()) {
+                    anyhow::bail!("tag `{}` is in more than one group", tag);
+                }
+            }
+            if let Some(default) = group.default.as_ref().filter(|d| !group.tags.contains(d)) {
+                anyhow::bail!("group `{}`: default `{}` is not one of its tags", group.name, default);
+            }
+        }
+        let optional = |patterns: &[String]| -> anyhow::Result<Option<GlobSet>> {
+            if patterns.is_empty() { Ok(None) } else { config::glob_set(patterns).map(Some) }
+        };
+        let mut compiled = rules.iter()
+            .map(|rule| {
+                if rule.tag.trim().is_empty() {
+                    anyhow::bail!("a tag rule has no `tag`");
+                }
+                let context = |err: anyhow::Error| anyhow::anyhow!("tag `{}`: {}", rule.tag, err);
+                Ok(Rule {
+                    tag: rule.tag.clone(),
+                    priority: rule.priority,
+                    names: optional(&rule.names).map_err(context)?,
+                    body: optional(&rule.body).map_err(context)?,
+                    headers: optional(&rule.headers).map_err(context)?,
+                    categories: optional(&rule.categories).map_err(context)?,
+                    tags: rule.tags.clone(),
+                    recursive: rule.recursive,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        compiled.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        Ok(Self { rules: compiled, groups })
+    }
+
+    /// Add the tags of the matching rules to `func`, `words` being those of
+    /// its body (see `c_parser::FunctionBody`). Tags already there come from
+    /// the sources or a manual and win within their group.
+    pub fn tag(&self, func: &mut FunctionMetadata, words: &[String]) {
+        let explicit = std::mem::take(&mut func.tags);
+        let mut have: HashSet<String> = explicit.iter().cloned().collect();
+        // a rule may need the tag of another: apply them until none is added
+        let mut fired = vec![false; self.rules.len()];
+        loop {
+            let mut changed = false;
+            for (i, rule) in self.rules.iter().enumerate() {
+                if !fired[i] && rule.matches(func, words, &have) {
+                    fired[i] = true;
+                    have.insert(rule.tag.clone());
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut tags: Vec<String> = Vec::new();
+        for (rule, _) in self.rules.iter().zip(&fired).filter(|(_, fired)| **fired) {
+            if !tags.contains(&rule.tag) && !explicit.contains(&rule.tag) {
+                tags.push(rule.tag.clone());
+            }
+        }
+        tags.extend(explicit.iter().cloned());
+        for group in &self.groups {
+            // explicit first, then by priority: the order of `tags` so far
+            let keep = explicit.iter().chain(&tags).find(|t| group.tags.contains(t)).cloned()
+                .or_else(|| group.default.clone());
+            tags.retain(|t| !group.tags.contains(t));
+            tags.extend(keep);
+        }
+        let mut seen = HashSet::new();
+        tags.retain(|t| seen.insert(t.clone()));
+        func.tags = tags;
+    }
+}
+
+impl Rule {
+    fn matches(&self, func: &FunctionMetadata, words: &[String], have: &HashSet<String>) -> bool {
+        self.names.as_ref().is_none_or(|g| g.is_match(&func.name))
+            && self.body.as_ref().is_none_or(|g| words.iter().any(|w| g.is_match(w)))
+            && self.headers.as_ref().is_none_or(|g| func.header.as_ref().is_some_and(|h| g.is_match(h)))
+            && self.categories.as_ref().is_none_or(|g| g.is_match(&func.category_path))
+            && (self.tags.is_empty() || self.tags.iter().any(|t| have.contains(t)))
+            && (!self.recursive || words.contains(&func.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tagger(rules: &str) -> Tagger {
+        let tags: Tags = toml::from_str(&format!("builtin = false\n{}", rules)).unwrap();
+        Tagger::new(&tags).unwrap()
+    }
+
+    fn function(name: &str, tags: &[&str]) -> FunctionMetadata {
+        let mut func: FunctionMetadata =
+            serde_json::from_str(&format!(r#"{{"name": "{}", "category": "str"}}"#, name)).unwrap();
+        func.category_path = "str".to_string();
+        func.tags = tags.iter().map(|t| t.to_string()).collect();
+        func
+    }
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    const LEVELS: &str = r#"
+        [[group]]
+        name = "difficulty"
+        tags = ["easy", "hard"]
+        default = "easy"
+    "#;
+
+    #[test]
+    fn highest_priority_wins_within_a_group() {
+        let tagger = tagger(&format!(r#"{}
+            [[rule]]
+            tag = "easy"
+            names = ["ft_*"]
+            [[rule]]
+            tag = "hard"
+            priority = 10
+            body = ["malloc"]
+        "#, LEVELS));
+        let mut func = function("ft_strdup", &[]);
+        tagger.tag(&mut func, &words(&["malloc", "return"]));
+        assert_eq!(func.tags, ["hard"]);
+
+        let mut func = function("ft_strlen", &[]);
+        tagger.tag(&mut func, &words(&["while", "return"]));
+        assert_eq!(func.tags, ["easy"]);
+    }
+
+    #[test]
+    fn explicit_tags_win_and_groups_fall_back_to_their_default() {
+        let tagger = tagger(&format!(r#"{}
+            [[rule]]
+            tag = "hard"
+            priority = 10
+            names = ["ft_split"]
+        "#, LEVELS));
+        let mut func = function("ft_split", &["easy"]);
+        tagger.tag(&mut func, &[]);
+        assert_eq!(func.tags, ["easy"]);
+
+        let mut func = function("ft_putchar", &[]);
+        tagger.tag(&mut func, &[]);
+        assert_eq!(func.tags, ["easy"]);
+    }
+
+    #[test]
+    fn rules_can_depend_on_other_tags_and_recursion() {
+        let tagger = tagger(r#"
+            [[rule]]
+            tag = "sorting"
+            tags = ["recursive"]
+            names = ["*sort*"]
+            [[rule]]
+            tag = "recursive"
+            recursive = true
+        "#);
+        let mut func = function("ft_quicksort", &[]);
+        tagger.tag(&mut func, &words(&["ft_quicksort", "if"]));
+        assert_eq!(func.tags, ["sorting", "recursive"]);
+
+        let mut func = function("ft_bubblesort", &[]);
+        tagger.tag(&mut func, &words(&["while"]));
+        assert!(func.tags.is_empty());
+    }
+
+    #[test]
+    fn a_tag_may_belong_to_one_group_only() {
+        let tags: Tags = toml::from_str(r#"
+            builtin = false
+            [[group]]
+            name = "a"
+            tags = ["x"]
+            [[group]]
+            name = "b"
+            tags = ["x"]
+        "#).unwrap();
+        assert!(Tagger::new(&tags).is_err());
+    }
+
+    #[test]
+    fn configured_groups_replace_builtin_ones_by_name() {
+        let tags: Tags = toml::from_str(r#"
+            [[group]]
+            name = "difficulty"
+            tags = ["simple", "tricky"]
+        "#).unwrap();
+        let groups = groups(&tags);
+        let difficulty: Vec<_> = groups.iter().filter(|g| g.name == DIFFICULTY_GROUP).collect();
+        assert_eq!(difficulty.len(), 1);
+        assert_eq!(difficulty[0].tags, ["simple", "tricky"]);
+    }
+}
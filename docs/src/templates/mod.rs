@@ -1 +1,161 @@
 // Templates module
+//
+// The site is rendered from Handlebars templates. `{{value}}` escapes for
+// HTML text and quoted attributes; `{{js value}}` and `{{url value}}` escape
+// for JS string literals and URL paths. The default theme is
+// embedded in the binary; a theme directory given on the command line
+// overrides any template of the same name and may ship extra assets
+// (a `styles.css`, images, scripts) copied as-is into the output.
+
+use crate::escape;
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+use include_dir::{include_dir, Dir};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+static DEFAULT_THEME: Dir = include_dir!("$CARGO_MANIFEST_DIR/themes/default");
+
+const TEMPLATE_EXT: &str = "hbs";
+
+pub struct Theme {
+    registry: Handlebars<'static>,
+    dir: Option<PathBuf>,
+}
+
+impl Theme {
+    /// Built-in templates, overridden by those found in `dir`.
+    pub fn load(dir: Option<&Path>) -> anyhow::Result<Self> {
+        let mut registry = Handlebars::new();
+        // `{{x}}` is HTML-escaped; these cover the other contexts
+        registry.register_helper("js", Box::new(js_helper));
+        registry.register_helper("url", Box::new(url_helper));
+
+        register_embedded(&mut registry, &DEFAULT_THEME)?;
+
+        if let Some(dir) = dir {
+            if !dir.is_dir() {
+                anyhow::bail!("theme directory not found: {}", dir.display());
+            }
+            for e in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+                let path = e.path();
+                if e.file_type().is_file() && path.extension().and_then(|s| s.to_str()) == Some(TEMPLATE_EXT) {
+                    let rel = path.strip_prefix(dir).unwrap_or(path);
+                    registry.register_template_file(&template_name(rel), path)
+                        .map_err(|err| anyhow::anyhow!("{}: {}", path.display(), err))?;
+                    println!("  🎨 Theme template: {}", rel.display());
+                }
+            }
+        }
+
+        Ok(Self { registry, dir: dir.map(Path::to_path_buf) })
+    }
+
+    pub fn render<T: Serialize>(&self, name: &str, context: &T) -> anyhow::Result<String> {
+        Ok(self.registry.render(name, context)?)
+    }
+
+    /// Copy everything in the theme directory that is not a template.
+    pub fn copy_assets(&self, output: &Path) -> anyhow::Result<()> {
+        let Some(dir) = &self.dir else { return Ok(()) };
+        for e in WalkDir::new(dir).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+            let path = e.path();
+            if !e.file_type().is_file() || path.extension().and_then(|s| s.to_str()) == Some(TEMPLATE_EXT) {
+                continue;
+            }
+            let dest = output.join(path.strip_prefix(dir).unwrap_or(path));
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(path, &dest)?;
+        }
+        Ok(())
+    }
+}
+
+fn param_string(h: &Helper) -> String {
+    match h.param(0).map(|p| p.value()) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => String::new(),
+        Some(v) => v.to_string(),
+    }
+}
+
+// `onclick="show({{js name}})"`: a quoted JS string literal
+fn js_helper(h: &Helper, _: &Handlebars, _: &Context, _: &mut RenderContext, out: &mut dyn Output) -> HelperResult {
+    out.write(&escape::js_string(&param_string(h)))?;
+    Ok(())
+}
+
+// `href="#/category/{{url path}}"`: a percent-encoded path
+fn url_helper(h: &Helper, _: &Handlebars, _: &Context, _: &mut RenderContext, out: &mut dyn Output) -> HelperResult {
+    out.write(&escape::html(&escape::url_path(&param_string(h))))?;
+    Ok(())
+}
+
+fn register_embedded(registry: &mut Handlebars<'static>, dir: &Dir) -> anyhow::Result<()> {
+    for file in dir.files() {
+        if file.path().extension().and_then(|s| s.to_str()) != Some(TEMPLATE_EXT) {
+            continue;
+        }
+        let source = file.contents_utf8()
+            .ok_or_else(|| anyhow::anyhow!("template {} is not UTF-8", file.path().display()))?;
+        registry.register_template_string(&template_name(file.path()), source)?;
+    }
+    for sub in dir.dirs() {
+        register_embedded(registry, sub)?;
+    }
+    Ok(())
+}
+
+// `partials/function-card.hbs` is used as `{{> function-card}}`
+fn template_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("libft-docs-theme-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn templates_are_named_by_file_stem() {
+        assert_eq!(template_name(Path::new("partials/function-card.hbs")), "function-card");
+        let theme = Theme::load(None).unwrap();
+        for name in ["index", "home", "category", "function", "headers", "header-file", "function-card"] {
+            assert!(theme.registry.has_template(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn theme_dirs_override_templates_and_ship_assets() {
+        let dir = temp_dir("override");
+        fs::create_dir_all(dir.join("pages")).unwrap();
+        fs::write(dir.join("pages/home.hbs"), "<a onclick=\"go({{js name}})\" href=\"#/{{url path}}\">{{name}}</a>").unwrap();
+        fs::write(dir.join("logo.svg"), "<svg/>").unwrap();
+        let theme = Theme::load(Some(&dir)).unwrap();
+        let html = theme.render("home", &json!({ "name": "a'<b>", "path": "x y/z" })).unwrap();
+        assert_eq!(html, "<a onclick=\"go('a\\u0027\\u003cb\\u003e')\" href=\"#/x%20y/z\">a&#x27;&lt;b&gt;</a>");
+
+        let output = dir.join("out");
+        theme.copy_assets(&output).unwrap();
+        assert!(output.join("logo.svg").is_file());
+        assert!(!output.join("pages/home.hbs").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_missing_theme_dir_is_an_error() {
+        assert!(Theme::load(Some(Path::new("/nonexistent/theme"))).is_err());
+    }
+}
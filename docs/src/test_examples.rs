@@ -0,0 +1,114 @@
+//! Examples harvested from the test suite: test files are `.c` files under a
+//! `tests/` (or `test/`) directory, `test_*.c` or `*_test.c`. Every test
+//! function calling an `ft_*` function becomes one of its examples; in
+//! `main`, each top-level block calling it does.
+
+use crate::c_parser::{self, CodeSpan};
+use crate::config::Selection;
+use crate::doc_comment::dedent;
+use crate::{Example, LibraryMetadata, PLACEHOLDER_EXAMPLE};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// Examples harvested per function, first test files first
+const MAX_PER_FUNCTION: usize = 3;
+
+/// Whether `path` (relative to the source directory) is part of the test suite.
+pub fn is_test_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else { return false };
+    if !name.ends_with(".c") && !name.ends_with(".h") {
+        return false;
+    }
+    name.starts_with("test_")
+        || name.ends_with("_test.c")
+        || path.parent().is_some_and(|dir| dir.components().any(|c| matches!(c.as_os_str().to_str(), Some("tests" | "test"))))
+}
+
+/// Attach the examples found in the test files below `source` that
+/// `selection` picks to the functions they call, replacing placeholder
+/// examples.
+pub fn harvest(metadata: &mut LibraryMetadata, source: &Path, selection: &Selection) {
+    let files: Vec<_> = selection.files(source).into_iter()
+        .filter(|p| p.extension().is_some_and(|x| x == "c"))
+        .filter(|p| is_test_file(p.strip_prefix(source).unwrap_or(p)))
+        .collect();
+
+    let mut found: BTreeMap<String, Vec<Example>> = BTreeMap::new();
+    for path in files {
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        let rel = path.strip_prefix(source).unwrap_or(&path).to_string_lossy().to_string();
+        for (name, example) in file_examples(&content, &rel) {
+            if metadata.functions.contains_key(&name) {
+                found.entry(name).or_default().push(example);
+            }
+        }
+    }
+
+    let mut harvested = 0;
+    for (name, examples) in found {
+        let Some(func) = metadata.functions.get_mut(&name) else { continue };
+        func.examples.retain(|e| !e.code.contains(PLACEHOLDER_EXAMPLE));
+        let count = examples.len().min(MAX_PER_FUNCTION);
+        func.examples.extend(examples.into_iter().take(MAX_PER_FUNCTION));
+        harvested += count;
+    }
+    if harvested > 0 {
+        println!("🧪 Harvested {} examples from the tests", harvested);
+    }
+}
+
+// (called function, example) for every test function or `main` block
+fn file_examples(content: &str, rel: &str) -> Vec<(String, Example)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut out = Vec::new();
+    for body in c_parser::function_bodies(content) {
+        let pieces: Vec<(&CodeSpan, String)> = if body.name == "main" && !body.blocks.is_empty() {
+            body.blocks.iter().enumerate().map(|(i, b)| (b, format!("main, block {}", i + 1))).collect()
+        } else {
+            vec![(&body.span, body.name.clone())]
+        };
+        for (span, title) in pieces {
+            let code = dedent(&lines[span.first_line - 1..span.last_line.min(lines.len())]);
+            for callee in span.calls.iter().filter(|c| c.starts_with("ft_")) {
+                out.push((callee.clone(), Example {
+                    title: format!("{} ({})", title, rel),
+                    code: code.clone(),
+                    output: None,
+                    source_file: Some(rel.to_string()),
+                    line: Some(span.first_line),
+                    output_at: None,
+                }));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_are_recognized_by_directory_or_name() {
+        assert!(is_test_file(Path::new("tests/strings.c")));
+        assert!(is_test_file(Path::new("test_strlen.c")));
+        assert!(is_test_file(Path::new("str/strlen_test.c")));
+        assert!(!is_test_file(Path::new("str/ft_strlen.c")));
+    }
+
+    #[test]
+    fn dedent_does_not_split_wide_whitespace() {
+        assert_eq!(dedent(&["  a", "\u{3000}b"]), "a\nb");
+        assert_eq!(dedent(&["\t\tx;", "", "\t\ty;"]), "x;\n\ny;");
+    }
+
+    #[test]
+    fn each_main_block_is_its_own_example() {
+        let src = "int\tmain(void)\n{\n\t{\n\t\tft_a(1);\n\t}\n\t{\n\t\tft_b(2);\n\t}\n\treturn (0);\n}\n";
+        let examples = file_examples(src, "tests/main.c");
+        let titles: Vec<_> = examples.iter().map(|(c, e)| (c.as_str(), e.title.as_str())).collect();
+        assert_eq!(titles, [("ft_a", "main, block 1 (tests/main.c)"), ("ft_b", "main, block 2 (tests/main.c)")]);
+        assert_eq!(examples[0].1.code, "{\n\tft_a(1);\n}");
+    }
+}
@@ -0,0 +1,123 @@
+//! Fonts and icons embedded in the generator, so an offline build (the
+//! default) makes no request outside the output directory.
+//!
+//! Icons are the subset of Font Awesome class names the templates use,
+//! drawn as SVG masks: `<i class="fas fa-folder">` keeps working, coloured
+//! by `currentColor`. Fonts are whatever `static/vendor/fonts` ships: Fira Sans
+//! and Source Code Pro, the fallbacks for Inter and JetBrains Mono.
+
+use include_dir::{include_dir, Dir};
+use std::fs;
+use std::path::Path;
+
+static VENDOR: Dir = include_dir!("$CARGO_MANIFEST_DIR/static/vendor");
+
+const FONT_FORMATS: &[(&str, &str)] = &[("woff2", "woff2"), ("woff", "woff"), ("ttf", "truetype"), ("otf", "opentype")];
+
+/// Write `vendor/icons.css`, `vendor/fonts.css` and the font files with
+/// their licenses.
+pub fn write_assets(output: &Path) -> anyhow::Result<()> {
+    let dir = output.join("vendor");
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("icons.css"), icons_css())?;
+
+    let mut faces = String::from("/* Vendored fonts; see static/vendor/fonts/README.md */\n");
+    if let Some(fonts) = VENDOR.get_dir("fonts") {
+        let mut files: Vec<_> = fonts.files().collect();
+        files.sort_by_key(|f| f.path());
+        for file in files {
+            let name = file.path().file_name().unwrap_or_default();
+            // the Open Font License travels with the fonts it covers
+            let license = name.to_str().is_some_and(|n| n.ends_with("-LICENSE.txt"));
+            let face = font_face(file.path());
+            if face.is_none() && !license {
+                continue;
+            }
+            fs::create_dir_all(dir.join("fonts"))?;
+            fs::write(dir.join("fonts").join(name), file.contents())?;
+            faces.push_str(face.as_deref().unwrap_or_default());
+        }
+    }
+    fs::write(dir.join("fonts.css"), faces)?;
+    Ok(())
+}
+
+fn icons_css() -> String {
+    let mut css = String::from(
+        ".fas, .far {\n  display: inline-block;\n  width: 1em;\n  height: 1em;\n  vertical-align: -0.125em;\n  \
+         background-color: currentColor;\n  -webkit-mask: var(--icon) center / contain no-repeat;\n  \
+         mask: var(--icon) center / contain no-repeat;\n}\n",
+    );
+    let Some(icons) = VENDOR.get_dir("icons") else { return css };
+    let mut files: Vec<_> = icons.files()
+        .filter(|f| f.path().extension().and_then(|e| e.to_str()) == Some("svg"))
+        .collect();
+    files.sort_by_key(|f| f.path());
+    for file in files {
+        let (Some(name), Some(svg)) = (file.path().file_stem().and_then(|s| s.to_str()), file.contents_utf8()) else {
+            continue;
+        };
+        css.push_str(&format!(".fa-{} {{ --icon: url(\"{}\"); }}\n", name, svg_data_uri(svg)));
+    }
+    css
+}
+
+// SVG markup as a `data:` URL usable inside a double-quoted `url()`
+fn svg_data_uri(svg: &str) -> String {
+    let mut uri = String::from("data:image/svg+xml,");
+    for c in svg.trim().chars() {
+        match c {
+            '"' => uri.push('\''),
+            '\n' | '\r' | '\t' => uri.push(' '),
+            '#' | '%' | '<' | '>' | '{' | '}' | '\\' => uri.push_str(&format!("%{:02X}", c as u32)),
+            c => uri.push(c),
+        }
+    }
+    uri
+}
+
+// `JetBrains_Mono-400italic.woff2` -> an `@font-face` for "JetBrains Mono"
+fn font_face(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let format = FONT_FORMATS.iter().find(|(e, _)| *e == ext)?.1;
+    let stem = path.file_stem()?.to_str()?;
+    let (family, variant) = stem.rsplit_once('-')?;
+    let (weight, style) = match variant.strip_suffix("italic") {
+        Some(weight) => (weight, "italic"),
+        None => (variant, "normal"),
+    };
+    let weight: u16 = weight.parse().ok()?;
+    let family = family.replace('_', " ");
+    let file = path.file_name()?.to_str()?;
+    Some(format!(
+        "@font-face {{\n  font-family: \"{family}\";\n  font-style: {style};\n  font-weight: {weight};\n  \
+         font-display: swap;\n  src: local(\"{family}\"), url(\"fonts/{file}\") format(\"{format}\");\n}}\n"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn font_files_name_their_face() {
+        let face = font_face(Path::new("fonts/Fira_Sans-700italic.woff2")).unwrap();
+        assert!(face.contains("font-family: \"Fira Sans\";\n  font-style: italic;\n  font-weight: 700;"));
+        assert!(face.contains("url(\"fonts/Fira_Sans-700italic.woff2\") format(\"woff2\")"));
+        assert!(font_face(Path::new("fonts/Fira_Sans-LICENSE.txt")).is_none());
+        assert!(font_face(Path::new("fonts/README.md")).is_none());
+    }
+
+    #[test]
+    fn svg_data_uris_survive_css_quoting() {
+        assert_eq!(svg_data_uri("<svg fill=\"#fff\">\n</svg>\n"), "data:image/svg+xml,%3Csvg fill='%23fff'%3E %3C/svg%3E");
+    }
+
+    #[test]
+    fn every_vendored_icon_gets_a_class() {
+        let css = icons_css();
+        let icons = VENDOR.get_dir("icons").map_or(0, |d| d.files().filter(|f| f.path().extension().is_some_and(|e| e == "svg")).count());
+        assert!(icons > 0);
+        assert_eq!(css.matches("--icon: url(\"data:image/svg+xml,").count(), icons);
+    }
+}